@@ -2,69 +2,184 @@ use core::hash::{Hash, Hasher};
 use core::mem;
 use core::time::Duration;
 
-use crate::{Instant, Stopwatch};
+use crate::{duration_from_nanos, Instant, Stopwatch};
 
-fn instant_eq<I: Instant>(lhs: I, rhs: I) -> bool {
+pub(crate) fn instant_eq<I: Instant>(lhs: I, rhs: I) -> bool {
     lhs.saturating_duration_since(rhs) == rhs.saturating_duration_since(lhs)
 }
 
+/// Moves `start` by as much of `elapsed` as is representable, using `shift`
+/// (either [`Instant::checked_sub`] or [`Instant::checked_add`]), returning
+/// the moved instant and how far it was moved.
+///
+/// Instants are opaque, so this binary searches the largest duration `d` at
+/// most `elapsed` for which `shift(start, d)` is representable, to the
+/// nanosecond.
+pub(crate) fn furthest_shift<I: Instant>(
+    start: I,
+    elapsed: Duration,
+    shift: fn(&I, Duration) -> Option<I>,
+) -> (I, Duration) {
+    let mut furthest = (start, Duration::ZERO);
+    let (mut lo, mut hi) = (0, elapsed.as_nanos());
+    while lo < hi {
+        let mid = hi - (hi - lo) / 2;
+        let dur = duration_from_nanos(mid).unwrap_or(Duration::MAX);
+        if let Some(instant) = shift(&start, dur) {
+            furthest = (instant, dur);
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    furthest
+}
+
+/// The canonical shape of a [`Stopwatch`], which defines its equality and
+/// hashing.
+///
+/// Stopwatches whose fields differ may measure the same elapsed time at every
+/// instant. Their canonical shapes are equal, and so are they.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{CanonicalStopwatch, Sw};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let now = Instant::now();
+/// let sw = Sw::from_raw(Duration::from_secs(1), Some(now));
+/// let shape = CanonicalStopwatch::Bounded(now - Duration::from_secs(1));
+/// assert_eq!(sw.canonical(), shape);
+/// ```
 #[derive(Clone, Copy, Debug)]
-pub(crate) enum Canonical<I: Instant> {
+pub enum CanonicalStopwatch<I: Instant> {
+    /// A stopped stopwatch with the given elapsed time.
     Stopped(Duration),
+    /// A running stopwatch whose elapsed time was zero at the given instant.
     Bounded(I),
-    Unbounded(()),
+    /// A running stopwatch whose elapsed time was never zero at any instant
+    /// representable by `I`.
+    ///
+    /// Since instants are opaque, the earliest start is found by binary
+    /// search, calling [`Instant::checked_sub`] about 100 times. This cost is
+    /// paid whenever an unbounded stopwatch is compared or hashed.
+    Unbounded {
+        /// The earliest instant the start could be moved back to.
+        start: I,
+        /// The elapsed time at `start`, which could not be moved into it.
+        excess: Duration,
+    },
 }
 
-impl<I: Instant> Canonical<I> {
-    pub fn new(sw: Stopwatch<I>) -> Self {
+impl<I: Instant> CanonicalStopwatch<I> {
+    /// Returns `true` if the stopwatch is running.
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        !self.is_stopped()
+    }
+
+    /// Returns `true` if the stopwatch is stopped.
+    #[must_use]
+    pub const fn is_stopped(&self) -> bool {
+        matches!(self, Self::Stopped(_))
+    }
+}
+
+impl<I: Instant> From<Stopwatch<I>> for CanonicalStopwatch<I> {
+    fn from(sw: Stopwatch<I>) -> Self {
         match sw.start {
             None => Self::Stopped(sw.elapsed),
 
-            Some(start) => match start.checked_sub(sw.elapsed) {
-                Some(sum) => {
+            Some(start) => {
+                if let Some(sum) = start.checked_sub(sw.elapsed) {
                     // # Case 1: t - d ∈ T
                     // all of the duration can be "moved" to the instant,
                     // leaving an implicit zero.
                     Self::Bounded(sum)
-                }
-
-                None => {
+                } else {
                     // # Case 2: t - d ∉ T
-                    // we consider all unbounded stopwatches to be equivalent.
-                    // it's tricky to do otherwise because of how opaque and
-                    // generic Instants are.
-                    Self::Unbounded(())
+                    // as much of the duration as possible is moved to the
+                    // instant, leaving an explicit excess. equivalent
+                    // stopwatches reach the same earliest instant, so they
+                    // are left with the same excess.
+                    let (start, moved) = furthest_shift(start, sw.elapsed, I::checked_sub);
+                    Self::Unbounded {
+                        start,
+                        excess: sw.elapsed.saturating_sub(moved),
+                    }
                 }
-            },
+            }
         }
     }
 }
 
-impl<I: Instant> PartialEq for Canonical<I> {
+impl<I: Instant> PartialEq for CanonicalStopwatch<I> {
     fn eq(&self, rhs: &Self) -> bool {
-        if mem::discriminant::<Self>(self) != mem::discriminant::<Self>(rhs) {
-            return false;
+        Shape::from(*self) == Shape::from(*rhs)
+    }
+}
+
+impl<I: Instant> Eq for CanonicalStopwatch<I> {}
+
+impl<I: Instant + Hash> Hash for CanonicalStopwatch<I> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Shape::from(*self).hash(state);
+    }
+}
+
+/// A canonical shape generic over the type of elapsed time, which defines
+/// equality and hashing for both [`CanonicalStopwatch`] and
+/// [`SignedStopwatch`](crate::SignedStopwatch). See [`CanonicalStopwatch`] for
+/// the meaning of each variant.
+#[derive(Clone, Copy)]
+pub(crate) enum Shape<I: Instant, D> {
+    Stopped(D),
+    Bounded(I),
+    Unbounded { start: I, excess: D },
+}
+
+impl<I: Instant> From<CanonicalStopwatch<I>> for Shape<I, Duration> {
+    fn from(canonical: CanonicalStopwatch<I>) -> Self {
+        match canonical {
+            CanonicalStopwatch::Stopped(elapsed) => Self::Stopped(elapsed),
+            CanonicalStopwatch::Bounded(start) => Self::Bounded(start),
+            CanonicalStopwatch::Unbounded { start, excess } => Self::Unbounded { start, excess },
         }
+    }
+}
 
-        match (*self, *rhs) {
+impl<I: Instant, D: Eq> PartialEq for Shape<I, D> {
+    fn eq(&self, rhs: &Self) -> bool {
+        match (self, rhs) {
             (Self::Stopped(lhs), Self::Stopped(rhs)) => lhs == rhs,
-            (Self::Bounded(lhs), Self::Bounded(rhs)) => instant_eq(lhs, rhs),
-            (Self::Unbounded(()), Self::Unbounded(())) => true,
-            _ => unreachable!(),
+            (Self::Bounded(lhs), Self::Bounded(rhs)) => instant_eq(*lhs, *rhs),
+            (
+                Self::Unbounded {
+                    start: lhs_start,
+                    excess: lhs_excess,
+                },
+                Self::Unbounded {
+                    start: rhs_start,
+                    excess: rhs_excess,
+                },
+            ) => instant_eq(*lhs_start, *rhs_start) && lhs_excess == rhs_excess,
+            _ => false,
         }
     }
 }
 
-impl<I: Instant + Hash> Hash for Canonical<I> {
+impl<I: Instant + Hash, D: Hash> Hash for Shape<I, D> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        let tag = mem::discriminant::<Canonical<I>>(self);
-
-        tag.hash(state);
+        mem::discriminant(self).hash(state);
 
         match self {
             Self::Stopped(d) => d.hash(state),
             Self::Bounded(t) => t.hash(state),
-            Self::Unbounded(()) => {}
+            Self::Unbounded { start, excess } => {
+                start.hash(state);
+                excess.hash(state);
+            }
         }
     }
 }
@@ -0,0 +1,363 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use crate::{Instant, Stopwatch};
+
+/// A stopwatch whose elapsed time never exceeds a cap.
+///
+/// Readings of the elapsed time are clamped to the cap. Once the stopwatch is
+/// observed past the cap by a mutating method, or by
+/// [`enforce_cap_at`](Self::enforce_cap_at), it stops with exactly the cap
+/// elapsed.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::CappedStopwatch;
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let start = Instant::now();
+/// let mut bank = CappedStopwatch::new_started_at(Duration::from_secs(60), start);
+///
+/// let later = start + Duration::from_secs(90);
+/// assert_eq!(bank.elapsed_at(later), Duration::from_secs(60));
+/// assert_eq!(bank.cap_instant(), Some(start + Duration::from_secs(60)));
+///
+/// assert!(bank.enforce_cap_at(later));
+/// assert!(bank.is_stopped());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CappedStopwatch<I: Instant> {
+    inner: Stopwatch<I>,
+    cap: Duration,
+}
+
+impl<I: Instant> CappedStopwatch<I> {
+    /// Returns a stopped stopwatch with no elapsed time, capped at `cap`.
+    #[must_use]
+    pub const fn new(cap: Duration) -> Self {
+        Self {
+            inner: Stopwatch::new(),
+            cap,
+        }
+    }
+
+    /// Returns a running stopwatch with no elapsed time, capped at `cap`,
+    /// started now.
+    #[must_use]
+    pub fn new_started(cap: Duration) -> Self {
+        Self::new_started_at(cap, I::now())
+    }
+
+    /// Returns a running stopwatch with no elapsed time, capped at `cap`,
+    /// started at the given instant.
+    #[must_use]
+    pub const fn new_started_at(cap: Duration, start: I) -> Self {
+        Self {
+            inner: Stopwatch::new_started_at(start),
+            cap,
+        }
+    }
+
+    /// Caps `stopwatch` at `cap`. If its elapsed time already exceeds `cap`,
+    /// it is clamped.
+    #[must_use]
+    pub fn from_stopwatch(mut stopwatch: Stopwatch<I>, cap: Duration) -> Self {
+        if stopwatch.elapsed > cap {
+            stopwatch = Stopwatch::with_elapsed(cap);
+        }
+        Self {
+            inner: stopwatch,
+            cap,
+        }
+    }
+
+    /// Returns the underlying stopwatch, which may run past the cap until the
+    /// cap is [enforced](Self::enforce_cap_at).
+    #[must_use]
+    pub const fn stopwatch(&self) -> &Stopwatch<I> {
+        &self.inner
+    }
+
+    /// Returns the maximum elapsed time.
+    #[must_use]
+    pub const fn cap(&self) -> Duration {
+        self.cap
+    }
+
+    /// Returns `true` if the stopwatch is running.
+    ///
+    /// # Notes
+    ///
+    /// A stopwatch past its cap is still running until the cap is enforced.
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        self.inner.is_running()
+    }
+
+    /// Returns `true` if the stopwatch is stopped.
+    #[must_use]
+    pub const fn is_stopped(&self) -> bool {
+        self.inner.is_stopped()
+    }
+
+    /// Returns the total time elapsed, clamped to the cap.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at(I::now())
+    }
+
+    /// Returns the total time elapsed, measured as if the current time were
+    /// `anchor`, clamped to the cap.
+    ///
+    /// # Notes
+    ///
+    /// `anchor` saturates to the last instant the stopwatch was started.
+    #[must_use]
+    pub fn elapsed_at(&self, anchor: I) -> Duration {
+        self.inner
+            .checked_elapsed_at(anchor)
+            .map_or(self.cap, |elapsed| elapsed.min(self.cap))
+    }
+
+    /// Returns the time left until the cap is reached.
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.remaining_at(I::now())
+    }
+
+    /// Returns the time left until the cap is reached, measured as if the
+    /// current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// `anchor` saturates to the last instant the stopwatch was started.
+    #[must_use]
+    pub fn remaining_at(&self, anchor: I) -> Duration {
+        self.cap.saturating_sub(self.elapsed_at(anchor))
+    }
+
+    /// Returns `true` if the cap has been reached.
+    #[must_use]
+    pub fn is_capped(&self) -> bool {
+        self.is_capped_at(I::now())
+    }
+
+    /// Returns `true` if the cap has been reached, measured as if the current
+    /// time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// `anchor` saturates to the last instant the stopwatch was started.
+    #[must_use]
+    pub fn is_capped_at(&self, anchor: I) -> bool {
+        self.elapsed_at(anchor) == self.cap
+    }
+
+    /// Returns the instant at which the cap is reached, if the stopwatch keeps
+    /// running. If it is stopped, or the instant is not representable,
+    /// returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// If the cap was already reached, this is the instant it was reached at.
+    #[must_use]
+    pub fn cap_instant(&self) -> Option<I> {
+        let start = self.inner.start?;
+        start.checked_add(self.cap.saturating_sub(self.inner.elapsed))
+    }
+
+    /// Stops the stopwatch with exactly the cap elapsed if the cap has been
+    /// reached. Returns `true` if the stopwatch was stopped.
+    pub fn enforce_cap(&mut self) -> bool {
+        self.enforce_cap_at(I::now())
+    }
+
+    /// Stops the stopwatch with exactly the cap elapsed if the cap has been
+    /// reached, measured as if the current time were `anchor`. Returns `true`
+    /// if the stopwatch was stopped.
+    ///
+    /// # Notes
+    ///
+    /// `anchor` saturates to the last instant the stopwatch was started.
+    pub fn enforce_cap_at(&mut self, anchor: I) -> bool {
+        if self.is_running() && self.is_capped_at(anchor) {
+            self.inner = Stopwatch::with_elapsed(self.cap);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Starts measuring the time elapsed. If the cap has been reached, the
+    /// stopwatch stays stopped.
+    pub fn start(&mut self) {
+        self.start_at(I::now());
+    }
+
+    /// Starts measuring the time elapsed as if the current time were `anchor`.
+    /// If the cap has been reached, the stopwatch stays stopped.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::start_at`] for notes about the chronology of
+    /// `anchor`.
+    pub fn start_at(&mut self, anchor: I) {
+        self.enforce_cap_at(anchor);
+        if !self.is_capped_at(anchor) {
+            self.inner.start_at(anchor);
+        }
+    }
+
+    /// Stops measuring the time elapsed since the last start, clamping the
+    /// elapsed time to the cap.
+    pub fn stop(&mut self) {
+        self.stop_at(I::now());
+    }
+
+    /// Stops measuring the time elapsed since the last start as if the current
+    /// time were `anchor`, clamping the elapsed time to the cap.
+    ///
+    /// # Notes
+    ///
+    /// If `anchor` is earlier than the last start, there is no effect on the
+    /// elapsed time.
+    pub fn stop_at(&mut self, anchor: I) {
+        self.inner.stop_at(anchor);
+        self.inner.elapsed = self.inner.elapsed.min(self.cap);
+    }
+
+    /// Toggles whether the stopwatch is running or stopped.
+    pub fn toggle(&mut self) {
+        self.toggle_at(I::now());
+    }
+
+    /// Toggles whether the stopwatch is running or stopped, as if the current
+    /// time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`start_at`](Self::start_at) and [`stop_at`](Self::stop_at) for
+    /// notes about the chronology of `anchor`.
+    pub fn toggle_at(&mut self, anchor: I) {
+        if self.is_running() {
+            self.stop_at(anchor);
+        } else {
+            self.start_at(anchor);
+        }
+    }
+
+    /// Stops and resets the elapsed time to zero, keeping the cap.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Resets the elapsed time to zero without affecting whether the stopwatch
+    /// is running, keeping the cap.
+    pub fn reset_in_place(&mut self) {
+        self.reset_in_place_at(I::now());
+    }
+
+    /// Resets the elapsed time to zero without affecting whether the stopwatch
+    /// is running, as if the current time were `anchor`, keeping the cap.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::reset_in_place_at`] for notes about the chronology
+    /// of `anchor`.
+    pub fn reset_in_place_at(&mut self, anchor: I) {
+        self.inner.reset_in_place_at(anchor);
+    }
+
+    /// Adds `dur` to the total elapsed time. If the cap is exceeded, the
+    /// total elapsed time is set to the cap.
+    ///
+    /// # Notes
+    ///
+    /// Like [`Stopwatch::saturating_add`], this only adds to the time
+    /// elapsed before the last start.
+    #[must_use]
+    pub fn saturating_add(mut self, dur: Duration) -> Self {
+        self.inner.elapsed = self.inner.elapsed.saturating_add(dur).min(self.cap);
+        self
+    }
+
+    /// Adds `dur` to the total elapsed time. If the cap would be exceeded,
+    /// returns [`None`].
+    #[must_use]
+    pub fn checked_add(self, dur: Duration) -> Option<Self> {
+        self.checked_add_at(dur, I::now())
+    }
+
+    /// Adds `dur` to the total elapsed time, as if the current time were
+    /// `anchor`. If the cap would be exceeded, returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// `anchor` saturates to the last instant the stopwatch was started.
+    #[must_use]
+    pub fn checked_add_at(mut self, dur: Duration, anchor: I) -> Option<Self> {
+        if self.elapsed_at(anchor).checked_add(dur)? > self.cap {
+            return None;
+        }
+        self.inner = self.inner.checked_add(dur)?;
+        Some(self)
+    }
+
+    /// Subtracts `dur` from the total elapsed time. If underflow occurred,
+    /// the total elapsed time is set to [`Duration::ZERO`].
+    #[must_use]
+    pub fn saturating_sub(self, dur: Duration) -> Self {
+        self.saturating_sub_at(dur, I::now())
+    }
+
+    /// Subtracts `dur` from the total elapsed time, as if the current time
+    /// were `anchor`. If underflow occurred, the total elapsed time is set to
+    /// [`Duration::ZERO`].
+    ///
+    /// # Notes
+    ///
+    /// - If the cap was reached, the stopwatch is stopped at the cap, and
+    ///   *then* `dur` is subtracted from the cap.
+    ///
+    /// - `anchor` saturates to the last instant the stopwatch was started.
+    #[must_use]
+    pub fn saturating_sub_at(mut self, dur: Duration, anchor: I) -> Self {
+        self.enforce_cap_at(anchor);
+        self.inner = self.inner.saturating_sub_at(dur, anchor);
+        self
+    }
+
+    /// Subtracts `dur` from the total elapsed time. If underflow occurred,
+    /// returns [`None`].
+    #[must_use]
+    pub fn checked_sub(self, dur: Duration) -> Option<Self> {
+        self.checked_sub_at(dur, I::now())
+    }
+
+    /// Subtracts `dur` from the total elapsed time, as if the current time
+    /// were `anchor`. If underflow occurred, returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// - If the cap was reached, the stopwatch is stopped at the cap, and
+    ///   *then* `dur` is subtracted from the cap.
+    ///
+    /// - `anchor` saturates to the last instant the stopwatch was started.
+    #[must_use]
+    pub fn checked_sub_at(mut self, dur: Duration, anchor: I) -> Option<Self> {
+        self.enforce_cap_at(anchor);
+        self.inner = self.inner.checked_sub_at(dur, anchor)?;
+        Some(self)
+    }
+}
+
+impl<I: Instant> From<CappedStopwatch<I>> for Stopwatch<I> {
+    /// Returns the underlying stopwatch, discarding the cap.
+    fn from(capped: CappedStopwatch<I>) -> Self {
+        capped.inner
+    }
+}
@@ -0,0 +1,252 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate std;
+
+use core::fmt;
+use core::time::Duration;
+use std::io;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Instant, Stopwatch};
+
+/// The process ID of all exported events.
+const PID: u32 = 1;
+
+/// A recording of timings which can be exported in the [Trace Event
+/// Format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU)
+/// understood by `chrome://tracing` and [Perfetto](https://ui.perfetto.dev).
+///
+/// Spans are exported as complete events, laps as instant events, and each
+/// thread ID is shown as a separate track. Timestamps are measured relative
+/// to an origin instant.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{ChromeTrace, Sw};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let origin = Instant::now();
+/// let mut trace = ChromeTrace::new(origin);
+/// trace.thread_name(1, "main");
+///
+/// let mut sw = Sw::new_started_at(origin);
+/// trace.lap("parsed", 1, origin + Duration::from_micros(250));
+/// trace.record_stop_at("compile", 1, &mut sw, origin + Duration::from_millis(1));
+///
+/// let json = trace.to_string();
+/// assert!(json.contains(r#"{"name":"compile","ph":"X","ts":0.000,"dur":1000.000,"pid":1,"tid":1}"#));
+/// assert!(json.contains(r#"{"name":"parsed","ph":"i","s":"t","ts":250.000,"pid":1,"tid":1}"#));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ChromeTrace<I: Instant> {
+    origin: I,
+    events: Vec<Event>,
+}
+
+#[derive(Clone, Debug)]
+struct Event {
+    name: String,
+    tid: u64,
+    kind: EventKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum EventKind {
+    Complete { ts: Duration, dur: Duration },
+    Instant { ts: Duration },
+    ThreadName,
+}
+
+impl<I: Instant> ChromeTrace<I> {
+    /// Returns an empty trace whose timestamps are measured relative to
+    /// `origin`.
+    #[must_use]
+    pub const fn new(origin: I) -> Self {
+        Self {
+            origin,
+            events: Vec::new(),
+        }
+    }
+
+    /// Returns the instant timestamps are measured relative to.
+    #[must_use]
+    pub const fn origin(&self) -> I {
+        self.origin
+    }
+
+    /// Returns the number of recorded events.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if no events have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Discards all recorded events.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Names the track of the thread `tid`.
+    pub fn thread_name(&mut self, tid: u64, name: impl Into<String>) {
+        self.events.push(Event {
+            name: name.into(),
+            tid,
+            kind: EventKind::ThreadName,
+        });
+    }
+
+    /// Records a span from `start` to `end` on the track of the thread `tid`,
+    /// as a complete event.
+    ///
+    /// # Notes
+    ///
+    /// If `start` is earlier than the origin, it saturates to the origin. If
+    /// `end` is earlier than `start`, the span has zero duration.
+    pub fn span(&mut self, name: impl Into<String>, tid: u64, start: I, end: I) {
+        let ts = start.saturating_duration_since(self.origin);
+        let dur = end.saturating_duration_since(start);
+        self.events.push(Event {
+            name: name.into(),
+            tid,
+            kind: EventKind::Complete { ts, dur },
+        });
+    }
+
+    /// Records a lap at `anchor` on the track of the thread `tid`, as an
+    /// instant event.
+    ///
+    /// # Notes
+    ///
+    /// If `anchor` is earlier than the origin, it saturates to the origin.
+    pub fn lap(&mut self, name: impl Into<String>, tid: u64, anchor: I) {
+        let ts = anchor.saturating_duration_since(self.origin);
+        self.events.push(Event {
+            name: name.into(),
+            tid,
+            kind: EventKind::Instant { ts },
+        });
+    }
+
+    /// Stops `sw`, recording the span since it was last started on the track
+    /// of the thread `tid`. If `sw` is stopped, nothing is recorded.
+    pub fn record_stop(&mut self, name: impl Into<String>, tid: u64, sw: &mut Stopwatch<I>) {
+        self.record_stop_at(name, tid, sw, I::now());
+    }
+
+    /// Stops `sw` as if the current time were `anchor`, recording the span
+    /// since it was last started on the track of the thread `tid`. If `sw` is
+    /// stopped, nothing is recorded.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    pub fn record_stop_at(
+        &mut self,
+        name: impl Into<String>,
+        tid: u64,
+        sw: &mut Stopwatch<I>,
+        anchor: I,
+    ) {
+        if let Some(start) = sw.start {
+            self.span(name, tid, start, anchor);
+            sw.stop_at(anchor);
+        }
+    }
+
+    /// Writes the trace as JSON to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing to `writer`.
+    pub fn write_json<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "{self}")
+    }
+}
+
+impl<I: Instant> fmt::Display for ChromeTrace<I> {
+    /// Formats the trace as JSON.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{\"traceEvents\":[")?;
+        for (i, event) in self.events.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            f.write_str("\n")?;
+            event.fmt(f)?;
+        }
+        f.write_str("\n]}")
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            EventKind::Complete { ts, dur } => write!(
+                f,
+                "{{\"name\":{},\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":{PID},\"tid\":{}}}",
+                JsonStr(&self.name),
+                Micros(ts),
+                Micros(dur),
+                self.tid,
+            ),
+            EventKind::Instant { ts } => write!(
+                f,
+                "{{\"name\":{},\"ph\":\"i\",\"s\":\"t\",\"ts\":{},\"pid\":{PID},\"tid\":{}}}",
+                JsonStr(&self.name),
+                Micros(ts),
+                self.tid,
+            ),
+            EventKind::ThreadName => write!(
+                f,
+                "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":{PID},\"tid\":{},\"args\":{{\"name\":{}}}}}",
+                self.tid,
+                JsonStr(&self.name),
+            ),
+        }
+    }
+}
+
+/// Formats a duration as fractional microseconds, with nanosecond precision.
+struct Micros(Duration);
+
+impl fmt::Display for Micros {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{:03}",
+            self.0.as_micros(),
+            self.0.subsec_nanos() % 1000
+        )
+    }
+}
+
+/// Formats a string as a quoted and escaped JSON string.
+struct JsonStr<'a>(&'a str);
+
+impl fmt::Display for JsonStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
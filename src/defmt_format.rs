@@ -0,0 +1,42 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate defmt;
+
+use defmt::{Format, Formatter};
+
+use crate::{Instant, Stopwatch};
+
+impl<I: Instant + Format> Format for Stopwatch<I> {
+    /// Formats the elapsed time before the last start, in seconds, and the
+    /// start instant if running.
+    ///
+    /// The elapsed time is sent as whole seconds and nanoseconds, so no
+    /// floating-point or `core::fmt` machinery is involved on the device. As
+    /// with [`Debug`](core::fmt::Debug), the time elapsed since the start is
+    /// not included, since that depends on the time of measurement.
+    ///
+    /// # Support
+    ///
+    /// `I` (the [`Instant`] type used by the stopwatch) must implement
+    /// [`Format`].
+    fn format(&self, fmt: Formatter) {
+        if let Some(start) = self.start {
+            defmt::write!(
+                fmt,
+                "Stopwatch {{ elapsed: {=u64}.{=u32:09}s, running since {} }}",
+                self.elapsed.as_secs(),
+                self.elapsed.subsec_nanos(),
+                start,
+            );
+        } else {
+            defmt::write!(
+                fmt,
+                "Stopwatch {{ elapsed: {=u64}.{=u32:09}s, stopped }}",
+                self.elapsed.as_secs(),
+                self.elapsed.subsec_nanos(),
+            );
+        }
+    }
+}
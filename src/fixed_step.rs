@@ -0,0 +1,190 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use crate::{duration_from_nanos, Instant, Stopwatch};
+
+/// An accumulator for running a simulation in fixed time steps.
+///
+/// Time elapsed between calls to [`advance`](Self::advance) is accumulated,
+/// and consumed in whole steps. The leftover time is reported as an
+/// interpolation factor between the previous and current simulation states.
+///
+/// To prevent a slow simulation from falling further and further behind, at
+/// most `max_steps` steps are run per call. Time beyond that is discarded.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::FixedStep;
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let mut timestep = FixedStep::<Instant>::new_started(Duration::from_millis(10), 5);
+///
+/// # for _ in 0..3 {
+/// // once per frame
+/// let steps = timestep.advance();
+/// for _ in 0..steps.count {
+///     // update the simulation by 10 ms...
+/// }
+/// // render, blending states by `steps.alpha`...
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FixedStep<I: Instant> {
+    step: Duration,
+    max_steps: u32,
+    accumulator: Stopwatch<I>,
+}
+
+/// The steps to run, as returned by [`FixedStep::advance`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Steps {
+    /// The number of whole steps to run.
+    pub count: u32,
+    /// The fraction of a step left in the accumulator, in `[0, 1)`.
+    pub alpha: f64,
+}
+
+impl<I: Instant> FixedStep<I> {
+    /// Returns a stopped accumulator with steps of `step`, running at most
+    /// `max_steps` steps per call to [`advance`](Self::advance).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    #[track_caller]
+    pub const fn new(step: Duration, max_steps: u32) -> Self {
+        Self::with_accumulator(step, max_steps, Stopwatch::new())
+    }
+
+    /// Returns a running accumulator with steps of `step`, running at most
+    /// `max_steps` steps per call to [`advance`](Self::advance).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    #[track_caller]
+    pub fn new_started(step: Duration, max_steps: u32) -> Self {
+        Self::new_started_at(step, max_steps, I::now())
+    }
+
+    /// Returns an accumulator with steps of `step`, running at most
+    /// `max_steps` steps per call to [`advance`](Self::advance), started at
+    /// the given instant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    #[track_caller]
+    pub const fn new_started_at(step: Duration, max_steps: u32, start: I) -> Self {
+        Self::with_accumulator(step, max_steps, Stopwatch::new_started_at(start))
+    }
+
+    /// Returns an accumulator with steps of `step`, running at most
+    /// `max_steps` steps per call to [`advance`](Self::advance), which
+    /// accumulates time with `accumulator`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    #[track_caller]
+    pub const fn with_accumulator(
+        step: Duration,
+        max_steps: u32,
+        accumulator: Stopwatch<I>,
+    ) -> Self {
+        assert!(!step.is_zero(), "fixed step must be non-zero");
+        Self {
+            step,
+            max_steps,
+            accumulator,
+        }
+    }
+
+    /// Returns the duration of a step.
+    #[must_use]
+    pub const fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Returns the maximum number of steps run per call to
+    /// [`advance`](Self::advance).
+    #[must_use]
+    pub const fn max_steps(&self) -> u32 {
+        self.max_steps
+    }
+
+    /// Returns the stopwatch accumulating time.
+    #[must_use]
+    pub const fn accumulator(&self) -> &Stopwatch<I> {
+        &self.accumulator
+    }
+
+    /// Returns the stopwatch accumulating time mutably.
+    ///
+    /// It can be stopped and started to pause the simulation.
+    #[must_use]
+    pub fn accumulator_mut(&mut self) -> &mut Stopwatch<I> {
+        &mut self.accumulator
+    }
+
+    /// Consumes the accumulated time in whole steps, returning the number of
+    /// steps to run and the interpolation factor.
+    pub fn advance(&mut self) -> Steps {
+        self.advance_at(I::now())
+    }
+
+    /// Consumes the accumulated time in whole steps as if the current time
+    /// were `anchor`, returning the number of steps to run and the
+    /// interpolation factor.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] and [`Stopwatch::set_in_place_at`] for
+    /// notes about the chronology of `anchor`, as well as what happens if
+    /// overflow occurs.
+    pub fn advance_at(&mut self, anchor: I) -> Steps {
+        let accumulated = self.accumulator.elapsed_at(anchor).as_nanos();
+        let step = self.step.as_nanos();
+
+        let whole = accumulated / step;
+        let leftover = duration_from_nanos(accumulated % step).unwrap_or(Duration::MAX);
+        let count = match u32::try_from(whole) {
+            Ok(count) if count <= self.max_steps => count,
+            _ => self.max_steps,
+        };
+
+        self.accumulator.set_in_place_at(leftover, anchor);
+        Steps {
+            count,
+            alpha: leftover.as_secs_f64() / self.step.as_secs_f64(),
+        }
+    }
+
+    /// Returns the interpolation factor of the time accumulated so far,
+    /// without consuming it. This may exceed `1.0` if whole steps are pending.
+    #[must_use]
+    pub fn alpha(&self) -> f64 {
+        self.alpha_at(I::now())
+    }
+
+    /// Returns the interpolation factor of the time accumulated so far,
+    /// measured as if the current time were `anchor`, without consuming it.
+    /// This may exceed `1.0` if whole steps are pending.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    #[must_use]
+    pub fn alpha_at(&self, anchor: I) -> f64 {
+        self.accumulator.elapsed_at(anchor).as_secs_f64() / self.step.as_secs_f64()
+    }
+}
@@ -0,0 +1,205 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::iter::Chain;
+use core::slice;
+use core::time::Duration;
+
+use crate::{duration_from_nanos, Instant};
+
+/// A frame timer keeping rolling statistics of the last `N` frame times.
+///
+/// Each call to [`tick`](Self::tick) marks the end of a frame, and records
+/// the time elapsed since the previous tick into a fixed ring buffer. The
+/// statistics are computed over the frames in the buffer.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::FrameTimer;
+/// # use std::time::Instant;
+/// let mut frames = FrameTimer::<Instant, 60>::new();
+/// # for _ in 0..3 {
+/// // once per frame
+/// frames.tick();
+/// # }
+/// if let Some(fps) = frames.fps() {
+///     println!("{fps:.1} fps, jitter {:?}", frames.jitter().unwrap());
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FrameTimer<I: Instant, const N: usize> {
+    deltas: [Duration; N],
+    /// The index of the next delta to be written.
+    head: usize,
+    len: usize,
+    last: Option<I>,
+}
+
+impl<I: Instant, const N: usize> FrameTimer<I, N> {
+    /// Returns a frame timer with no frames recorded.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            deltas: [Duration::ZERO; N],
+            head: 0,
+            len: 0,
+            last: None,
+        }
+    }
+
+    /// Marks the end of a frame, returning the time elapsed since the previous
+    /// tick. On the first tick, returns [`None`].
+    pub fn tick(&mut self) -> Option<Duration> {
+        self.tick_at(I::now())
+    }
+
+    /// Marks the end of a frame as if the current time were `anchor`,
+    /// returning the time elapsed since the previous tick. On the first tick,
+    /// returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// If `anchor` is before the previous tick, the frame time saturates to
+    /// zero.
+    pub fn tick_at(&mut self, anchor: I) -> Option<Duration> {
+        let delta = anchor.saturating_duration_since(self.last.replace(anchor)?);
+        if N > 0 {
+            self.deltas[self.head] = delta;
+            self.head = (self.head + 1) % N;
+            self.len = (self.len + 1).min(N);
+        }
+        Some(delta)
+    }
+
+    /// Forgets all frames, including the previous tick.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the number of frame times in the window.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no frame times have been recorded.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of frame times in the window.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns an iterator over the frame times in the window, from oldest to
+    /// newest.
+    pub fn deltas(&self) -> Deltas<'_> {
+        // until the buffer is full, the head is the number of deltas
+        let (newer, older) = if self.len < N {
+            (&self.deltas[..self.len], &[][..])
+        } else {
+            self.deltas.split_at(self.head)
+        };
+        Deltas {
+            inner: older.iter().chain(newer.iter()),
+        }
+    }
+
+    /// Returns the time of the most recent frame.
+    #[must_use]
+    pub fn last_delta(&self) -> Option<Duration> {
+        self.deltas().next_back()
+    }
+
+    /// Returns the shortest frame time in the window.
+    #[must_use]
+    pub fn min(&self) -> Option<Duration> {
+        self.deltas().min()
+    }
+
+    /// Returns the longest frame time in the window.
+    #[must_use]
+    pub fn max(&self) -> Option<Duration> {
+        self.deltas().max()
+    }
+
+    /// Returns the mean frame time in the window.
+    #[must_use]
+    pub fn average(&self) -> Option<Duration> {
+        if self.is_empty() {
+            return None;
+        }
+        let sum: u128 = self.deltas().map(|delta| delta.as_nanos()).sum();
+        Some(duration_from_nanos(sum / self.len as u128).unwrap_or(Duration::MAX))
+    }
+
+    /// Returns the frame rate in frames per second, based on the mean frame
+    /// time in the window. If the mean frame time is zero, returns [`None`].
+    #[must_use]
+    pub fn fps(&self) -> Option<f64> {
+        let average = self.average()?;
+        if average.is_zero() {
+            None
+        } else {
+            Some(average.as_secs_f64().recip())
+        }
+    }
+
+    /// Returns the frame pacing jitter, which is the mean absolute deviation
+    /// of the frame times in the window from their mean.
+    #[must_use]
+    pub fn jitter(&self) -> Option<Duration> {
+        let average = self.average()?.as_nanos();
+        let deviation: u128 = self
+            .deltas()
+            .map(|delta| {
+                let delta = delta.as_nanos();
+                delta.max(average) - delta.min(average)
+            })
+            .sum();
+        Some(duration_from_nanos(deviation / self.len as u128).unwrap_or(Duration::MAX))
+    }
+}
+
+impl<I: Instant, const N: usize> Default for FrameTimer<I, N> {
+    /// Returns the default frame timer. Same as calling [`FrameTimer::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An iterator over the frame times of a [`FrameTimer`], from oldest to
+/// newest.
+///
+/// This is returned by [`FrameTimer::deltas`].
+#[derive(Clone, Debug)]
+pub struct Deltas<'a> {
+    inner: Chain<slice::Iter<'a, Duration>, slice::Iter<'a, Duration>>,
+}
+
+impl Iterator for Deltas<'_> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        self.inner.next().copied()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Deltas<'_> {
+    fn next_back(&mut self) -> Option<Duration> {
+        self.inner.next_back().copied()
+    }
+}
+
+impl ExactSizeIterator for Deltas<'_> {}
+
+impl core::iter::FusedIterator for Deltas<'_> {}
@@ -0,0 +1,336 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use crate::{Instant, Stopwatch};
+
+/// A stage of a time control, such as "40 moves in 90 minutes".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimeControl {
+    /// The time added to each player's clock when they enter the stage.
+    pub time: Duration,
+    /// The number of moves each player must make to complete the stage, or
+    /// [`None`] if the stage lasts for the rest of the game.
+    pub moves: Option<u32>,
+    /// The bonus time given for each move made during the stage.
+    pub bonus: Bonus,
+}
+
+impl TimeControl {
+    /// Returns a stage adding `time` to each player's clock, lasting `moves`
+    /// moves, and giving `bonus` for each move.
+    #[must_use]
+    pub const fn new(time: Duration, moves: Option<u32>, bonus: Bonus) -> Self {
+        Self { time, moves, bonus }
+    }
+}
+
+/// The bonus time given for each move of a [`TimeControl`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Bonus {
+    /// Fischer increment: the duration is added to the player's clock after
+    /// each move.
+    Increment(Duration),
+    /// Simple (US) delay: the player's clock does not run for the first part
+    /// of each move, up to the duration.
+    Delay(Duration),
+    /// Bronstein delay: after each move, the time used by the move is given
+    /// back, up to the duration.
+    Bronstein(Duration),
+}
+
+/// A clock alternating between `N` players, such as a chess clock.
+///
+/// The time spent by each player is measured with their own [`Stopwatch`].
+/// When the active player presses the clock, their stopwatch is stopped and
+/// the next player's is started at the same anchor, so no time is lost
+/// between moves.
+///
+/// A game follows a sequence of [`TimeControl`] stages. After completing the
+/// moves of a stage, a player enters the next stage and its time is added to
+/// their clock. If the last stage has a number of moves, it is repeated.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{Bonus, GameClock, TimeControl};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let mins = |mins: u64| Duration::from_secs(mins * 60);
+/// let increment = Bonus::Increment(Duration::from_secs(30));
+/// let stages = [
+///     // 40 moves in 90 minutes, then 30 minutes for the rest of the game
+///     TimeControl::new(mins(90), Some(40), increment),
+///     TimeControl::new(mins(30), None, increment),
+/// ];
+///
+/// let start = Instant::now();
+/// let mut clock = GameClock::<Instant, 2>::new(&stages);
+/// clock.start_at(0, start);
+///
+/// // white thinks for 10 seconds, then it's black's turn
+/// let next = clock.press_at(start + Duration::from_secs(10));
+/// assert_eq!(next, Some(1));
+/// assert_eq!(
+///     clock.remaining_at(0, start + Duration::from_secs(10)),
+///     mins(90) + Duration::from_secs(20),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct GameClock<'a, I: Instant, const N: usize> {
+    stages: &'a [TimeControl],
+    players: [Player<I>; N],
+    /// The active player, or [`None`] if the game hasn't started.
+    active: Option<usize>,
+    /// Measures the current move, including any delay.
+    turn: Stopwatch<I>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Player<I: Instant> {
+    /// The time charged to the player.
+    spent: Stopwatch<I>,
+    /// The total time given to the player, including bonuses.
+    budget: Duration,
+    moves: u32,
+    stage: usize,
+    stage_moves: u32,
+}
+
+impl<'a, I: Instant, const N: usize> GameClock<'a, I, N> {
+    /// Returns a clock following the time control `stages`, with no active
+    /// player.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stages` is empty.
+    #[must_use]
+    #[track_caller]
+    pub fn new(stages: &'a [TimeControl]) -> Self {
+        assert!(!stages.is_empty(), "time control must have a stage");
+        Self {
+            stages,
+            players: [Player {
+                spent: Stopwatch::new(),
+                budget: stages[0].time,
+                moves: 0,
+                stage: 0,
+                stage_moves: 0,
+            }; N],
+            active: None,
+            turn: Stopwatch::new(),
+        }
+    }
+
+    /// Returns the player to move, or [`None`] if the game hasn't started.
+    #[must_use]
+    pub const fn active(&self) -> Option<usize> {
+        self.active
+    }
+
+    /// Returns `true` if the clock of the active player is running.
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        self.turn.is_running()
+    }
+
+    /// Returns the number of moves `player` has made.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `player` is not less than `N`.
+    #[must_use]
+    #[track_caller]
+    pub const fn moves(&self, player: usize) -> u32 {
+        self.players[player].moves
+    }
+
+    /// Returns the time control stage `player` is in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `player` is not less than `N`.
+    #[must_use]
+    #[track_caller]
+    pub fn stage(&self, player: usize) -> &'a TimeControl {
+        time_control(self.stages, self.players[player].stage)
+    }
+
+    /// Returns the time left on the clock of `player`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `player` is not less than `N`.
+    #[must_use]
+    #[track_caller]
+    pub fn remaining(&self, player: usize) -> Duration {
+        self.remaining_at(player, I::now())
+    }
+
+    /// Returns the time left on the clock of `player`, measured as if the
+    /// current time were `anchor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `player` is not less than `N`.
+    #[must_use]
+    #[track_caller]
+    pub fn remaining_at(&self, player: usize, anchor: I) -> Duration {
+        let player = &self.players[player];
+        player
+            .budget
+            .saturating_sub(player.spent.elapsed_at(anchor))
+    }
+
+    /// Returns the first player whose time has run out, if any.
+    #[must_use]
+    pub fn flagged(&self) -> Option<usize> {
+        self.flagged_at(I::now())
+    }
+
+    /// Returns the first player whose time has run out, measured as if the
+    /// current time were `anchor`, if any.
+    #[must_use]
+    pub fn flagged_at(&self, anchor: I) -> Option<usize> {
+        (0..N).find(|&player| self.remaining_at(player, anchor).is_zero())
+    }
+
+    /// Returns the instant at which the active player's time runs out, if
+    /// their clock keeps running. If the clock is paused, or the instant is
+    /// not representable, returns [`None`].
+    #[must_use]
+    pub fn flag_instant(&self) -> Option<I> {
+        let player = &self.players[self.active?];
+        let start = player.spent.start?;
+        start.checked_add(player.budget.saturating_sub(player.spent.elapsed))
+    }
+
+    /// Starts the game with `player` to move.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `player` is not less than `N`.
+    #[track_caller]
+    pub fn start(&mut self, player: usize) {
+        self.start_at(player, I::now());
+    }
+
+    /// Starts the game with `player` to move, as if the current time were
+    /// `anchor`. If a player was already to move, their clock is stopped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `player` is not less than `N`.
+    #[track_caller]
+    pub fn start_at(&mut self, player: usize, anchor: I) {
+        assert!(player < N, "player index out of bounds");
+        self.pause_at(anchor);
+        self.active = Some(player);
+        self.turn = Stopwatch::new();
+        self.resume_at(anchor);
+    }
+
+    /// Ends the active player's move and starts the next player's, returning
+    /// the next player.
+    ///
+    /// If the game hasn't started, or the active player's time has run out,
+    /// nothing happens and [`None`] is returned.
+    pub fn press(&mut self) -> Option<usize> {
+        self.press_at(I::now())
+    }
+
+    /// Ends the active player's move and starts the next player's as if the
+    /// current time were `anchor`, returning the next player.
+    ///
+    /// If the game hasn't started, or the active player's time has run out,
+    /// nothing happens and [`None`] is returned. If the clock is paused, the
+    /// next player's clock is also paused.
+    pub fn press_at(&mut self, anchor: I) -> Option<usize> {
+        let active = self.active?;
+        if self.remaining_at(active, anchor).is_zero() {
+            return None;
+        }
+
+        let running = self.is_running();
+        let move_time = self.turn.elapsed_at(anchor);
+        self.pause_at(anchor);
+
+        let bonus = self.stage(active).bonus;
+        let stages = self.stages;
+        let player = &mut self.players[active];
+        match bonus {
+            Bonus::Increment(increment) => {
+                player.budget = player.budget.saturating_add(increment);
+            }
+            Bonus::Delay(_) => {}
+            Bonus::Bronstein(delay) => {
+                player.budget = player.budget.saturating_add(move_time.min(delay));
+            }
+        }
+
+        player.moves = player.moves.saturating_add(1);
+        player.stage_moves = player.stage_moves.saturating_add(1);
+        if Some(player.stage_moves) == time_control(stages, player.stage).moves {
+            player.stage = player.stage.saturating_add(1);
+            player.stage_moves = 0;
+            let time = time_control(stages, player.stage).time;
+            player.budget = player.budget.saturating_add(time);
+        }
+
+        let next = (active + 1) % N;
+        self.active = Some(next);
+        self.turn = Stopwatch::new();
+        if running {
+            self.resume_at(anchor);
+        }
+        Some(next)
+    }
+
+    /// Pauses the active player's clock.
+    pub fn pause(&mut self) {
+        self.pause_at(I::now());
+    }
+
+    /// Pauses the active player's clock, as if the current time were
+    /// `anchor`.
+    pub fn pause_at(&mut self, anchor: I) {
+        if let Some(active) = self.active {
+            self.turn.stop_at(anchor);
+            self.players[active].spent.stop_at(anchor);
+        }
+    }
+
+    /// Resumes the active player's clock.
+    pub fn resume(&mut self) {
+        self.resume_at(I::now());
+    }
+
+    /// Resumes the active player's clock, as if the current time were
+    /// `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// With a simple delay, the part of the delay not yet used by the current
+    /// move still applies.
+    pub fn resume_at(&mut self, anchor: I) {
+        let active = match self.active {
+            Some(active) if self.turn.is_stopped() => active,
+            _ => return,
+        };
+        let delay = match self.stage(active).bonus {
+            Bonus::Delay(delay) => delay.saturating_sub(self.turn.elapsed),
+            Bonus::Increment(_) | Bonus::Bronstein(_) => Duration::ZERO,
+        };
+        self.turn.start_at(anchor);
+        // the clock doesn't run until the delay has passed
+        let start = anchor.checked_add(delay).unwrap_or(anchor);
+        self.players[active].spent.start_at(start);
+    }
+}
+
+/// Returns the stage at `index`, repeating the last stage.
+fn time_control(stages: &[TimeControl], index: usize) -> &TimeControl {
+    &stages[index.min(stages.len() - 1)]
+}
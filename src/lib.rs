@@ -26,14 +26,18 @@
 //!
 //! # Features
 //!
-//! | Name         | Implies | Description                                                                                                                               |
-//! |--------------|---------|-------------------------------------------------------------------------------------------------------------------------------------------|
-//! | `default`    |         | Enabled by default.                                                                                                                       |
-//! | `std`        |         | Depends on the standard library. Implements [`Instant`] for `std::time::{Instant, SystemTime}`. Exposes `Sw` and `SystemSw` type aliases. |
-//! | `tokio`      | `std`   | Implements [`Instant`] for `tokio::time::Instant`. Exposes `TokioSw` type alias.                                                          |
-//! | `coarsetime` | `std`   | Implements [`Instant`] for `coarsetime::Instant`. Exposes `CoarseSw` type alias.                                                          |
-//! | `quanta`     | `std`   | Implements [`Instant`] for `quanta::Instant`. Exposes `QuantaSw` type alias.                                                              |
-//! | `time`       | `std`   | Deprecated. Implements [`Instant`] for `time::Instant`. Exposes `TimeSw` type alias.                                                      |
+//! | Name         | Implies | Description                                                                                                                                                                                                                                                              |
+//! |--------------|---------|--------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
+//! | `default`    |         | Enabled by default.                                                                                                                                                                                                                                                      |
+//! | `std`        |         | Depends on the standard library. Implements [`Instant`] for `std::time::{Instant, SystemTime}`. Exposes `Sw` and `SystemSw` type aliases. Exposes `Profiler`, `ChromeTrace`, `WallClock`, `WallStopwatch`, `VirtualClock`, `TaskTracker`, `WatchdogMonitor` and `probe`. |
+//! | `tokio`      | `std`   | Implements [`Instant`] for `tokio::time::Instant`. Exposes `TokioSw` type alias.                                                                                                                                                                                         |
+//! | `coarsetime` | `std`   | Implements [`Instant`] for `coarsetime::Instant`. Exposes `CoarseSw` type alias.                                                                                                                                                                                         |
+//! | `quanta`     | `std`   | Implements [`Instant`] for `quanta::Instant`. Exposes `QuantaSw` type alias.                                                                                                                                                                                             |
+//! | `defmt`      |         | Implements `defmt::Format` for [`Stopwatch`], for compact logging on embedded targets.                                                                                                                                                                                   |
+//! | `metrics`    | `std`   | Adds methods recording stopwatches into `metrics` histograms. Exposes `HistogramGuard` and `StopwatchRegistry`.                                                                                                                                                          |
+//! | `proptest`   | `std`   | Exposes `StopwatchStrategies` for generating stopwatches with `proptest`, and `ReferenceStopwatch`, a model for differential testing.                                                                                                                                    |
+//! | `tracing`    | `std`   | Exposes `StopwatchLayer`, a `tracing_subscriber` layer measuring span busy and idle time.                                                                                                                                                                                |
+//! | `time`       | `std`   | Deprecated. Implements [`Instant`] for `time::Instant`. Exposes `TimeSw` type alias.                                                                                                                                                                                     |
 //!
 //! ## `no_std` support
 //!
@@ -55,13 +59,91 @@
 
 extern crate core;
 
+use core::time::Duration;
+
 mod canonical;
+mod capped;
+#[cfg(feature = "std")]
+mod chrome_trace;
+#[cfg(feature = "defmt")]
+mod defmt_format;
+mod fixed_step;
+mod frame_timer;
+mod game_clock;
 mod instant;
 mod instant_impls;
+#[cfg(feature = "metrics")]
+mod metrics_export;
+mod multi_pause;
+mod openmetrics;
+mod phase_timer;
+#[cfg(feature = "std")]
+mod probe;
+#[cfg(feature = "std")]
+mod profiler;
+mod signed;
+mod slice_ext;
+mod snapshot;
 mod stopwatch;
+mod sum;
+#[cfg(feature = "std")]
+mod task_tracker;
+#[cfg(feature = "proptest")]
+mod testing;
+#[cfg(feature = "tracing")]
+mod tracing_layer;
+#[cfg(feature = "std")]
+mod virtual_clock;
+#[cfg(feature = "std")]
+mod wall_clock;
+mod watchdog;
+#[cfg(feature = "std")]
+mod watchdog_monitor;
 
+pub use crate::canonical::CanonicalStopwatch;
+pub use crate::capped::CappedStopwatch;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::chrome_trace::ChromeTrace;
+pub use crate::fixed_step::{FixedStep, Steps};
+pub use crate::frame_timer::{Deltas, FrameTimer};
+pub use crate::game_clock::{Bonus, GameClock, TimeControl};
 pub use crate::instant::Instant;
+#[cfg(feature = "metrics")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "metrics")))]
+pub use crate::metrics_export::{HistogramGuard, RegistryIter, StopwatchRegistry};
+pub use crate::multi_pause::MultiPauseStopwatch;
+pub use crate::openmetrics::OpenMetrics;
+pub use crate::phase_timer::{Phase, PhaseBoundaries, PhaseBoundary, PhaseTimer};
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::probe::{probe, probe_with, ProbeReport};
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::profiler::{Nodes, ProfileNode, Profiler};
+pub use crate::signed::{SignedDuration, SignedStopwatch};
+pub use crate::slice_ext::{ElapsedAll, StopwatchIterExt, StopwatchSliceExt};
+pub use crate::snapshot::{Snapshot, Snapshots};
 pub use crate::stopwatch::Stopwatch;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::task_tracker::{TaskTracker, Tasks};
+#[cfg(feature = "proptest")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "proptest")))]
+pub use crate::testing::{ReferenceStopwatch, StopwatchStrategies};
+#[cfg(feature = "tracing")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "tracing")))]
+pub use crate::tracing_layer::{SpanTimings, StopwatchLayer};
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::virtual_clock::{VirtualClock, VirtualDomain, VirtualInstant};
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::wall_clock::{WallClock, WallStopwatch};
+pub use crate::watchdog::Watchdog;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use crate::watchdog_monitor::{WatchdogHandle, WatchdogMonitor};
 
 /// Alias to [`Stopwatch`] using the standard library's
 /// [`Instant`](std::time::Instant) type.
@@ -102,5 +184,14 @@ pub type QuantaSw = Stopwatch<::quanta::Instant>;
 )]
 pub type TimeSw = Stopwatch<::time::Instant>;
 
+/// Converts nanoseconds to a [`Duration`]. If overflow occurs, returns
+/// [`None`].
+pub(crate) fn duration_from_nanos(nanos: u128) -> Option<Duration> {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+    let secs = u64::try_from(nanos / NANOS_PER_SEC).ok()?;
+    #[allow(clippy::cast_possible_truncation)]
+    Some(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+}
+
 #[cfg(test)]
 mod tests;
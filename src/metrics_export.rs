@@ -0,0 +1,306 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate metrics;
+extern crate std;
+
+use core::iter::FusedIterator;
+use core::slice;
+use core::time::Duration;
+use metrics::{Histogram, Key, Level, Metadata};
+use std::vec::Vec;
+
+use crate::{Instant, Stopwatch};
+
+/// Metadata of the gauges reported by [`StopwatchRegistry::flush`].
+static METADATA: Metadata<'static> = Metadata::new("libsw_core", Level::INFO, Some("libsw_core"));
+
+impl<I: Instant> Stopwatch<I> {
+    /// Records the total time elapsed into `histogram`, in seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::Sw;
+    /// let sw = Sw::new_started();
+    /// sw.record_elapsed(&metrics::histogram!("request_seconds"));
+    /// ```
+    pub fn record_elapsed(&self, histogram: &Histogram) {
+        self.record_elapsed_at(histogram, I::now());
+    }
+
+    /// Records the total time elapsed into `histogram`, in seconds, measured
+    /// as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`elapsed_at`](Self::elapsed_at) for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    pub fn record_elapsed_at(&self, histogram: &Histogram, anchor: I) {
+        histogram.record(self.elapsed_at(anchor));
+    }
+
+    /// Records the total time elapsed into `histogram`, in seconds, and resets
+    /// the elapsed time to zero without affecting whether the stopwatch is
+    /// running. Returns the recorded lap time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::Sw;
+    /// let histogram = metrics::histogram!("frame_seconds");
+    /// let mut sw = Sw::new_started();
+    /// for _ in 0..3 {
+    ///     // render a frame...
+    ///     sw.record_lap(&histogram);
+    /// }
+    /// ```
+    pub fn record_lap(&mut self, histogram: &Histogram) -> Duration {
+        self.record_lap_at(histogram, I::now())
+    }
+
+    /// Records the total time elapsed into `histogram`, in seconds, and resets
+    /// the elapsed time to zero without affecting whether the stopwatch is
+    /// running, as if the current time were `anchor`. Returns the recorded lap
+    /// time.
+    ///
+    /// # Notes
+    ///
+    /// See [`elapsed_at`](Self::elapsed_at) and
+    /// [`reset_in_place_at`](Self::reset_in_place_at) for notes about the
+    /// chronology of `anchor`, as well as what happens if overflow occurs.
+    pub fn record_lap_at(&mut self, histogram: &Histogram, anchor: I) -> Duration {
+        let lap = self.elapsed_at(anchor);
+        histogram.record(lap);
+        self.reset_in_place_at(anchor);
+        lap
+    }
+}
+
+/// A guard which records the time elapsed since its creation into a
+/// [`Histogram`] when dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::HistogramGuard;
+/// # use std::time::Instant;
+/// fn handle_request() {
+///     let _guard = HistogramGuard::<Instant>::new(metrics::histogram!("request_seconds"));
+///     // handle the request...
+/// } // elapsed time is recorded here
+/// ```
+#[derive(Debug)]
+#[must_use = "if unused, the elapsed time is recorded immediately"]
+pub struct HistogramGuard<I: Instant> {
+    histogram: Histogram,
+    sw: Stopwatch<I>,
+    armed: bool,
+}
+
+impl<I: Instant> HistogramGuard<I> {
+    /// Returns a guard which will record into `histogram`, started now.
+    pub fn new(histogram: Histogram) -> Self {
+        Self::new_started_at(histogram, I::now())
+    }
+
+    /// Returns a guard which will record into `histogram`, started at the
+    /// given instant.
+    pub const fn new_started_at(histogram: Histogram, start: I) -> Self {
+        Self {
+            histogram,
+            sw: Stopwatch::new_started_at(start),
+            armed: true,
+        }
+    }
+
+    /// Returns the stopwatch measuring the time to be recorded.
+    ///
+    /// It can be stopped and started to exclude time from the recording.
+    pub fn stopwatch(&mut self) -> &mut Stopwatch<I> {
+        &mut self.sw
+    }
+
+    /// Records the time elapsed as if the current time were `anchor`,
+    /// consuming the guard. Returns the recorded time.
+    #[allow(clippy::must_use_candidate)]
+    pub fn record_at(mut self, anchor: I) -> Duration {
+        self.armed = false;
+        let elapsed = self.sw.elapsed_at(anchor);
+        self.histogram.record(elapsed);
+        elapsed
+    }
+
+    /// Consumes the guard without recording anything.
+    pub fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl<I: Instant> Drop for HistogramGuard<I> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.sw.record_elapsed(&self.histogram);
+        }
+    }
+}
+
+/// A collection of named stopwatches which can be reported to the installed
+/// [`metrics`] recorder at once.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{StopwatchRegistry, Sw};
+/// # use std::time::Instant;
+/// let mut registry = StopwatchRegistry::<Instant>::new();
+/// registry.insert("uptime_seconds", Sw::new_started());
+/// registry.insert("busy_seconds", Sw::new());
+///
+/// registry.get_mut("busy_seconds").unwrap().start();
+/// // ...
+/// registry.flush();
+/// ```
+#[derive(Clone, Debug)]
+pub struct StopwatchRegistry<I: Instant> {
+    entries: Vec<(Key, Stopwatch<I>)>,
+}
+
+impl<I: Instant> StopwatchRegistry<I> {
+    /// Returns an empty registry.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the number of stopwatches in the registry.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the registry contains no stopwatches.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts a stopwatch reported as `key`, returning the stopwatch it
+    /// replaced, if any.
+    pub fn insert(&mut self, key: impl Into<Key>, sw: Stopwatch<I>) -> Option<Stopwatch<I>> {
+        let key = key.into();
+        if let Some(old) = self.get_mut(key.clone()) {
+            return Some(core::mem::replace(old, sw));
+        }
+        self.entries.push((key, sw));
+        None
+    }
+
+    /// Removes and returns the stopwatch reported as `key`, if any.
+    pub fn remove(&mut self, key: impl Into<Key>) -> Option<Stopwatch<I>> {
+        let key = key.into();
+        let index = self.entries.iter().position(|(k, _)| *k == key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Returns a reference to the stopwatch reported as `key`, if any.
+    #[must_use]
+    pub fn get(&self, key: impl Into<Key>) -> Option<&Stopwatch<I>> {
+        let key = key.into();
+        self.entries
+            .iter()
+            .find_map(|(k, sw)| if *k == key { Some(sw) } else { None })
+    }
+
+    /// Returns a mutable reference to the stopwatch reported as `key`, if any.
+    #[must_use]
+    pub fn get_mut(&mut self, key: impl Into<Key>) -> Option<&mut Stopwatch<I>> {
+        let key = key.into();
+        self.entries
+            .iter_mut()
+            .find_map(|(k, sw)| if *k == key { Some(sw) } else { None })
+    }
+
+    /// Returns an iterator over the keys and stopwatches in the registry, in
+    /// insertion order.
+    #[must_use]
+    pub fn iter(&self) -> RegistryIter<'_, I> {
+        RegistryIter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Reports the total time elapsed of every stopwatch, running or stopped,
+    /// as a gauge in seconds.
+    pub fn flush(&self) {
+        self.flush_at(I::now());
+    }
+
+    /// Reports the total time elapsed of every stopwatch, running or stopped,
+    /// as a gauge in seconds, measured as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    pub fn flush_at(&self, anchor: I) {
+        metrics::with_recorder(|recorder| {
+            for (key, sw) in &self.entries {
+                recorder
+                    .register_gauge(key, &METADATA)
+                    .set(sw.elapsed_at(anchor));
+            }
+        });
+    }
+}
+
+impl<I: Instant> Default for StopwatchRegistry<I> {
+    /// Returns the default registry. Same as calling
+    /// [`StopwatchRegistry::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, I: Instant> IntoIterator for &'a StopwatchRegistry<I> {
+    type Item = (&'a Key, &'a Stopwatch<I>);
+    type IntoIter = RegistryIter<'a, I>;
+
+    fn into_iter(self) -> RegistryIter<'a, I> {
+        self.iter()
+    }
+}
+
+/// An iterator over the keys and stopwatches in a [`StopwatchRegistry`], in
+/// insertion order.
+///
+/// This is returned by [`StopwatchRegistry::iter`].
+#[derive(Clone, Debug)]
+pub struct RegistryIter<'a, I: Instant> {
+    inner: slice::Iter<'a, (Key, Stopwatch<I>)>,
+}
+
+impl<'a, I: Instant> Iterator for RegistryIter<'a, I> {
+    type Item = (&'a Key, &'a Stopwatch<I>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, sw)| (key, sw))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I: Instant> DoubleEndedIterator for RegistryIter<'_, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, sw)| (key, sw))
+    }
+}
+
+impl<I: Instant> ExactSizeIterator for RegistryIter<'_, I> {}
+
+impl<I: Instant> FusedIterator for RegistryIter<'_, I> {}
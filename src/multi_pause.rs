@@ -0,0 +1,295 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use crate::{Instant, Stopwatch};
+
+/// A stopwatch which can be paused for independent reasons of type `R`, and
+/// only runs while it is started and none of them are active.
+///
+/// Pausing for a reason which is already active, or resuming a reason which
+/// isn't, has no effect, so one source cannot undo the pause of another. The
+/// time spent paused is also tracked separately for each reason.
+///
+/// Up to `N` distinct reasons are tracked. A reason is tracked from the first
+/// time it pauses the stopwatch, so its paused time is kept after it is
+/// resumed. Once `N` reasons are tracked, pausing for any other reason has no
+/// effect.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::MultiPauseStopwatch;
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// #[derive(Clone, Copy, PartialEq, Eq)]
+/// enum Reason {
+///     Unfocused,
+///     Modal,
+/// }
+///
+/// let start = Instant::now();
+/// let at = |secs| start + Duration::from_secs(secs);
+/// let mut sw = MultiPauseStopwatch::<Instant, Reason, 2>::new_started_at(start);
+///
+/// sw.pause_at(Reason::Unfocused, at(1));
+/// sw.pause_at(Reason::Modal, at(2));
+/// sw.resume_at(Reason::Unfocused, at(3)); // still paused by the modal
+/// assert!(sw.is_paused());
+/// sw.resume_at(Reason::Modal, at(4));
+///
+/// assert_eq!(sw.elapsed_at(at(5)), Duration::from_secs(2));
+/// assert_eq!(sw.paused_time_at(Reason::Unfocused, at(5)), Duration::from_secs(2));
+/// assert_eq!(sw.paused_time_at(Reason::Modal, at(5)), Duration::from_secs(2));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MultiPauseStopwatch<I: Instant, R: Copy + Eq, const N: usize> {
+    stopwatch: Stopwatch<I>,
+    reasons: [Option<(R, Stopwatch<I>)>; N],
+    started: bool,
+}
+
+impl<I: Instant, R: Copy + Eq, const N: usize> MultiPauseStopwatch<I, R, N> {
+    /// Returns a stopped stopwatch with no pause reasons active. It runs once
+    /// [started](Self::start).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            stopwatch: Stopwatch::new(),
+            reasons: [None; N],
+            started: false,
+        }
+    }
+
+    /// Returns a running stopwatch with no pause reasons active, started now.
+    #[must_use]
+    pub fn new_started() -> Self {
+        Self::new_started_at(I::now())
+    }
+
+    /// Returns a running stopwatch with no pause reasons active, started at
+    /// the given instant.
+    #[must_use]
+    pub const fn new_started_at(start: I) -> Self {
+        Self {
+            stopwatch: Stopwatch::new_started_at(start),
+            reasons: [None; N],
+            started: true,
+        }
+    }
+
+    /// Returns the underlying stopwatch, which runs while the stopwatch is
+    /// started and no pause reasons are active.
+    #[must_use]
+    pub const fn stopwatch(&self) -> &Stopwatch<I> {
+        &self.stopwatch
+    }
+
+    /// Returns the total time elapsed while started with no pause reasons
+    /// active.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at(I::now())
+    }
+
+    /// Returns the total time elapsed while started with no pause reasons
+    /// active, measured as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    #[must_use]
+    pub fn elapsed_at(&self, anchor: I) -> Duration {
+        self.stopwatch.elapsed_at(anchor)
+    }
+
+    /// Returns `true` if the stopwatch is started and no pause reasons are
+    /// active.
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        self.stopwatch.is_running()
+    }
+
+    /// Returns `true` if the stopwatch is started, regardless of whether any
+    /// pause reasons are active.
+    #[must_use]
+    pub const fn is_started(&self) -> bool {
+        self.started
+    }
+
+    /// Returns `true` if any pause reason is active.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.active_reasons() > 0
+    }
+
+    /// Returns `true` if `reason` is active.
+    #[must_use]
+    pub fn is_paused_by(&self, reason: R) -> bool {
+        self.paused(reason).map_or(false, Stopwatch::is_running)
+    }
+
+    /// Returns the number of active pause reasons.
+    #[must_use]
+    pub fn active_reasons(&self) -> usize {
+        self.reasons
+            .iter()
+            .flatten()
+            .filter(|(_, sw)| sw.is_running())
+            .count()
+    }
+
+    /// Returns the total time `reason` has been active.
+    #[must_use]
+    pub fn paused_time(&self, reason: R) -> Duration {
+        self.paused_time_at(reason, I::now())
+    }
+
+    /// Returns the total time `reason` has been active, measured as if the
+    /// current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    #[must_use]
+    pub fn paused_time_at(&self, reason: R, anchor: I) -> Duration {
+        self.paused(reason)
+            .map_or(Duration::ZERO, |sw| sw.elapsed_at(anchor))
+    }
+
+    /// Starts the stopwatch, which runs from now if no pause reasons are
+    /// active. Returns `false` if it was already started.
+    pub fn start(&mut self) -> bool {
+        self.start_at(I::now())
+    }
+
+    /// Starts the stopwatch as if the current time were `anchor`. It runs from
+    /// `anchor` if no pause reasons are active. Returns `false` if it was
+    /// already started.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::start_at`] for notes about the chronology of
+    /// `anchor`.
+    pub fn start_at(&mut self, anchor: I) -> bool {
+        if self.started {
+            return false;
+        }
+        self.started = true;
+        if self.active_reasons() == 0 {
+            self.stopwatch.start_at(anchor);
+        }
+        true
+    }
+
+    /// Stops the stopwatch, so it no longer runs once all pause reasons are
+    /// deactivated. Returns `false` if it was already stopped.
+    pub fn stop(&mut self) -> bool {
+        self.stop_at(I::now())
+    }
+
+    /// Stops the stopwatch as if the current time were `anchor`, so it no
+    /// longer runs once all pause reasons are deactivated. Returns `false` if
+    /// it was already stopped.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    pub fn stop_at(&mut self, anchor: I) -> bool {
+        if !self.started {
+            return false;
+        }
+        self.started = false;
+        self.stopwatch.stop_at(anchor);
+        true
+    }
+
+    /// Activates `reason`, pausing the stopwatch if it was running. Returns
+    /// `false` if `reason` was already active, or if it isn't tracked and `N`
+    /// other reasons already are.
+    pub fn pause(&mut self, reason: R) -> bool {
+        self.pause_at(reason, I::now())
+    }
+
+    /// Activates `reason` as if the current time were `anchor`, pausing the
+    /// stopwatch if it was running. Returns `false` if `reason` was already
+    /// active, or if it isn't tracked and `N` other reasons already are.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    pub fn pause_at(&mut self, reason: R, anchor: I) -> bool {
+        if let Some(paused) = self.paused_mut(reason) {
+            if paused.is_running() {
+                return false;
+            }
+            paused.start_at(anchor);
+        } else if let Some(slot) = self.reasons.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some((reason, Stopwatch::new_started_at(anchor)));
+        } else {
+            return false;
+        }
+        self.stopwatch.stop_at(anchor);
+        true
+    }
+
+    /// Deactivates `reason`, resuming the stopwatch if it is started and no
+    /// other reasons are active. Returns `false` if `reason` was not active.
+    pub fn resume(&mut self, reason: R) -> bool {
+        self.resume_at(reason, I::now())
+    }
+
+    /// Deactivates `reason` as if the current time were `anchor`, resuming the
+    /// stopwatch if it is started and no other reasons are active. Returns
+    /// `false` if `reason` was not active.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::start_at`] and [`Stopwatch::stop_at`] for notes about
+    /// the chronology of `anchor`, as well as what happens if overflow occurs.
+    pub fn resume_at(&mut self, reason: R, anchor: I) -> bool {
+        match self.paused_mut(reason) {
+            Some(paused) if paused.is_running() => paused.stop_at(anchor),
+            _ => return false,
+        }
+        if self.started && self.active_reasons() == 0 {
+            self.stopwatch.start_at(anchor);
+        }
+        true
+    }
+}
+
+// private methods
+impl<I: Instant, R: Copy + Eq, const N: usize> MultiPauseStopwatch<I, R, N> {
+    /// Returns the stopwatch tracking the time `reason` has been active, if it
+    /// is tracked.
+    fn paused(&self, reason: R) -> Option<&Stopwatch<I>> {
+        self.reasons
+            .iter()
+            .flatten()
+            .find(|(r, _)| *r == reason)
+            .map(|(_, sw)| sw)
+    }
+
+    fn paused_mut(&mut self, reason: R) -> Option<&mut Stopwatch<I>> {
+        self.reasons
+            .iter_mut()
+            .flatten()
+            .find(|(r, _)| *r == reason)
+            .map(|(_, sw)| sw)
+    }
+}
+
+impl<I: Instant, R: Copy + Eq, const N: usize> Default for MultiPauseStopwatch<I, R, N> {
+    /// Returns the default stopwatch. Same as calling
+    /// [`MultiPauseStopwatch::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
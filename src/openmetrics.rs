@@ -0,0 +1,206 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::fmt;
+use core::time::Duration;
+
+use crate::{Instant, Stopwatch};
+
+/// Renders a set of named stopwatches in the [OpenMetrics text
+/// format](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md).
+///
+/// Every stopwatch is read at a single shared anchor, so all values are
+/// consistent with each other. For a metric family named `family`, the
+/// following metrics are rendered, with the name of each stopwatch as the
+/// `timer` label:
+///
+/// - `family_seconds` (gauge): the total time elapsed, in seconds.
+/// - `family_running` (gauge): `1` if the stopwatch is running, otherwise `0`.
+/// - `family_elapsed_seconds` (gauge histogram): the distribution of the total
+///   time elapsed across all stopwatches. This is only rendered if
+///   [buckets](Self::buckets) are set.
+///
+/// Elapsed time decreases whenever a stopwatch is reset or has time
+/// subtracted, so none of these metrics are counters. The histogram is built
+/// from the current state of each stopwatch on every scrape, rather than from
+/// accumulated observations.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{OpenMetrics, Sw};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let now = Instant::now();
+/// let timers = [
+///     ("build", Sw::with_elapsed(Duration::from_millis(1500))),
+///     ("deploy", Sw::new_started_at(now)),
+/// ];
+///
+/// let mut text = String::new();
+/// OpenMetrics::new("ci", &timers)
+///     .write_at(&mut text, now)
+///     .unwrap();
+/// assert!(text.contains("ci_seconds{timer=\"build\"} 1.5\n"));
+/// assert!(text.contains("ci_running{timer=\"deploy\"} 1\n"));
+/// assert!(text.ends_with("# EOF\n"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct OpenMetrics<'a, I: Instant> {
+    family: &'a str,
+    timers: &'a [(&'a str, Stopwatch<I>)],
+    buckets: Option<&'a [Duration]>,
+}
+
+impl<'a, I: Instant> OpenMetrics<'a, I> {
+    /// Returns a renderer of `timers`, as the metric family `family`.
+    ///
+    /// `family` should be a valid metric name, and is not escaped.
+    #[must_use]
+    pub const fn new(family: &'a str, timers: &'a [(&'a str, Stopwatch<I>)]) -> Self {
+        Self {
+            family,
+            timers,
+            buckets: None,
+        }
+    }
+
+    /// Also renders a histogram of the total time elapsed across the
+    /// stopwatches, with upper bounds `buckets`.
+    ///
+    /// `buckets` should be sorted in ascending order, and should not include
+    /// the implicit `+Inf` bucket.
+    #[must_use]
+    pub const fn buckets(mut self, buckets: &'a [Duration]) -> Self {
+        self.buckets = Some(buckets);
+        self
+    }
+
+    /// Writes the metric families, followed by the `# EOF` marker, to `w`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing to `w`.
+    pub fn write<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        self.write_at(w, I::now())
+    }
+
+    /// Writes the metric families, followed by the `# EOF` marker, to `w`,
+    /// measured as if the current time were `anchor`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing to `w`.
+    pub fn write_at<W: fmt::Write>(&self, w: &mut W, anchor: I) -> fmt::Result {
+        self.write_families_at(w, anchor)?;
+        w.write_str("# EOF\n")
+    }
+
+    /// Writes the metric families to `w`, measured as if the current time were
+    /// `anchor`, *without* the `# EOF` marker.
+    ///
+    /// This is useful for combining families into a single exposition.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing to `w`.
+    pub fn write_families_at<W: fmt::Write>(&self, w: &mut W, anchor: I) -> fmt::Result {
+        let family = self.family;
+
+        writeln!(w, "# TYPE {family}_seconds gauge")?;
+        writeln!(w, "# UNIT {family}_seconds seconds")?;
+        writeln!(w, "# HELP {family}_seconds Total time elapsed.")?;
+        for (name, sw) in self.timers {
+            writeln!(
+                w,
+                "{family}_seconds{{timer=\"{}\"}} {}",
+                Escaped(name),
+                Seconds(sw.elapsed_at(anchor)),
+            )?;
+        }
+
+        writeln!(w, "# TYPE {family}_running gauge")?;
+        writeln!(w, "# HELP {family}_running Whether the timer is running.")?;
+        for (name, sw) in self.timers {
+            writeln!(
+                w,
+                "{family}_running{{timer=\"{}\"}} {}",
+                Escaped(name),
+                u8::from(sw.is_running()),
+            )?;
+        }
+
+        if let Some(buckets) = self.buckets {
+            self.write_histogram(w, buckets, anchor)?;
+        }
+
+        Ok(())
+    }
+}
+
+// private methods
+impl<I: Instant> OpenMetrics<'_, I> {
+    fn write_histogram<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        buckets: &[Duration],
+        anchor: I,
+    ) -> fmt::Result {
+        let family = self.family;
+        let elapsed = || self.timers.iter().map(|(_, sw)| sw.elapsed_at(anchor));
+
+        writeln!(w, "# TYPE {family}_elapsed_seconds gaugehistogram")?;
+        writeln!(w, "# UNIT {family}_elapsed_seconds seconds")?;
+        writeln!(
+            w,
+            "# HELP {family}_elapsed_seconds Distribution of total time elapsed across timers."
+        )?;
+        for bound in buckets {
+            let count = elapsed().filter(|elapsed| elapsed <= bound).count();
+            writeln!(
+                w,
+                "{family}_elapsed_seconds_bucket{{le=\"{}\"}} {count}",
+                Seconds(*bound),
+            )?;
+        }
+        let count = self.timers.len();
+        let sum = elapsed().fold(Duration::ZERO, Duration::saturating_add);
+        writeln!(w, "{family}_elapsed_seconds_bucket{{le=\"+Inf\"}} {count}")?;
+        writeln!(w, "{family}_elapsed_seconds_gcount {count}")?;
+        writeln!(w, "{family}_elapsed_seconds_gsum {}", Seconds(sum))
+    }
+}
+
+/// Formats a duration as exact fractional seconds, with at least one decimal
+/// place.
+struct Seconds(Duration);
+
+impl fmt::Display for Seconds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut nanos = self.0.subsec_nanos();
+        let mut width = 9;
+        while width > 1 && nanos % 10 == 0 {
+            nanos /= 10;
+            width -= 1;
+        }
+        write!(f, "{}.{nanos:0width$}", self.0.as_secs())
+    }
+}
+
+/// Formats a string as an escaped label value.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                c => fmt::Write::write_char(f, c)?,
+            }
+        }
+        Ok(())
+    }
+}
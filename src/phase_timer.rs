@@ -0,0 +1,340 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::iter::FusedIterator;
+use core::time::Duration;
+
+use crate::{duration_from_nanos, Instant, Stopwatch};
+
+/// A timer stepping through a cycle of timed phases, optionally repeated a
+/// number of times.
+///
+/// Progress through the sequence is measured with a [`Stopwatch`], so
+/// stopping and starting the timer pauses and resumes the whole sequence
+/// exactly.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::PhaseTimer;
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let mins = |mins: u64| Duration::from_secs(mins * 60);
+/// // work and break four times, the last break being a long one
+/// let pomodoro = [
+///     mins(25), mins(5), mins(25), mins(5),
+///     mins(25), mins(5), mins(25), mins(15),
+/// ];
+///
+/// let start = Instant::now();
+/// let timer = PhaseTimer::new_started_at(&pomodoro, None, start);
+///
+/// let phase = timer.phase_at(start + mins(27)).unwrap();
+/// assert_eq!(phase.index, 1);
+/// assert_eq!(phase.remaining, mins(3));
+///
+/// let next = timer.boundaries_at(start).next().unwrap();
+/// assert_eq!(next.instant, start + mins(25));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PhaseTimer<'a, I: Instant> {
+    phases: &'a [Duration],
+    cycles: Option<u64>,
+    cycle: Duration,
+    sw: Stopwatch<I>,
+}
+
+/// The position of a [`PhaseTimer`] within its sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Phase {
+    /// The number of cycles completed before this phase.
+    pub cycle: u64,
+    /// The index of the phase within the cycle.
+    pub index: usize,
+    /// The time elapsed in the phase.
+    pub elapsed: Duration,
+    /// The time left in the phase.
+    pub remaining: Duration,
+}
+
+/// The end of a phase, as yielded by [`PhaseBoundaries`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PhaseBoundary<I: Instant> {
+    /// The instant at which the phase ends.
+    pub instant: I,
+    /// The number of cycles completed before the phase.
+    pub cycle: u64,
+    /// The index of the phase within the cycle.
+    pub index: usize,
+}
+
+impl<'a, I: Instant> PhaseTimer<'a, I> {
+    /// Returns a stopped timer at the start of a cycle of `phases`, repeated
+    /// `cycles` times, or forever if [`None`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the phases have a total duration of zero.
+    #[must_use]
+    #[track_caller]
+    pub fn new(phases: &'a [Duration], cycles: Option<u64>) -> Self {
+        Self::with_stopwatch(phases, cycles, Stopwatch::new())
+    }
+
+    /// Returns a running timer at the start of a cycle of `phases`, repeated
+    /// `cycles` times, or forever if [`None`], started now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the phases have a total duration of zero.
+    #[must_use]
+    #[track_caller]
+    pub fn new_started(phases: &'a [Duration], cycles: Option<u64>) -> Self {
+        Self::new_started_at(phases, cycles, I::now())
+    }
+
+    /// Returns a running timer at the start of a cycle of `phases`, repeated
+    /// `cycles` times, or forever if [`None`], started at the given instant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the phases have a total duration of zero.
+    #[must_use]
+    #[track_caller]
+    pub fn new_started_at(phases: &'a [Duration], cycles: Option<u64>, start: I) -> Self {
+        Self::with_stopwatch(phases, cycles, Stopwatch::new_started_at(start))
+    }
+
+    /// Returns a timer over a cycle of `phases`, repeated `cycles` times, or
+    /// forever if [`None`], whose progress is measured by `sw`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the phases have a total duration of zero.
+    #[must_use]
+    #[track_caller]
+    pub fn with_stopwatch(phases: &'a [Duration], cycles: Option<u64>, sw: Stopwatch<I>) -> Self {
+        let cycle = phases
+            .iter()
+            .fold(Duration::ZERO, |sum, phase| sum.saturating_add(*phase));
+        assert!(!cycle.is_zero(), "phases must have a non-zero duration");
+        Self {
+            phases,
+            cycles,
+            cycle,
+            sw,
+        }
+    }
+
+    /// Returns the stopwatch measuring progress through the sequence.
+    #[must_use]
+    pub const fn stopwatch(&self) -> &Stopwatch<I> {
+        &self.sw
+    }
+
+    /// Returns `true` if the timer is running.
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        self.sw.is_running()
+    }
+
+    /// Returns `true` if the timer is stopped.
+    #[must_use]
+    pub const fn is_stopped(&self) -> bool {
+        self.sw.is_stopped()
+    }
+
+    /// Starts or resumes the sequence.
+    pub fn start(&mut self) {
+        self.start_at(I::now());
+    }
+
+    /// Starts or resumes the sequence, as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::start_at`] for notes about the chronology of
+    /// `anchor`.
+    pub fn start_at(&mut self, anchor: I) {
+        if self.is_stopped() {
+            self.sw.start_at(anchor);
+        }
+    }
+
+    /// Pauses the sequence.
+    pub fn stop(&mut self) {
+        self.stop_at(I::now());
+    }
+
+    /// Pauses the sequence, as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    pub fn stop_at(&mut self, anchor: I) {
+        self.sw.stop_at(anchor);
+    }
+
+    /// Returns the active phase. If the sequence is finished, returns
+    /// [`None`].
+    #[must_use]
+    pub fn phase(&self) -> Option<Phase> {
+        self.phase_at(I::now())
+    }
+
+    /// Returns the active phase, measured as if the current time were
+    /// `anchor`. If the sequence is finished, returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    #[must_use]
+    pub fn phase_at(&self, anchor: I) -> Option<Phase> {
+        self.phase_of(self.sw.elapsed_at(anchor))
+    }
+
+    /// Returns the number of completed cycles.
+    #[must_use]
+    pub fn cycles_done(&self) -> u64 {
+        self.cycles_done_at(I::now())
+    }
+
+    /// Returns the number of completed cycles, measured as if the current time
+    /// were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    #[must_use]
+    pub fn cycles_done_at(&self, anchor: I) -> u64 {
+        let done = self.sw.elapsed_at(anchor).as_nanos() / self.cycle.as_nanos();
+        let done = u64::try_from(done).unwrap_or(u64::MAX);
+        self.cycles.map_or(done, |cycles| done.min(cycles))
+    }
+
+    /// Returns `true` if every cycle has been completed.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.is_finished_at(I::now())
+    }
+
+    /// Returns `true` if every cycle has been completed, measured as if the
+    /// current time were `anchor`.
+    #[must_use]
+    pub fn is_finished_at(&self, anchor: I) -> bool {
+        self.phase_at(anchor).is_none()
+    }
+
+    /// Returns an iterator over the upcoming ends of phases, starting with the
+    /// active phase.
+    ///
+    /// # Notes
+    ///
+    /// See [`boundaries_at`](Self::boundaries_at).
+    #[must_use]
+    pub fn boundaries(&self) -> PhaseBoundaries<'a, I> {
+        self.boundaries_at(I::now())
+    }
+
+    /// Returns an iterator over the upcoming ends of phases, starting with the
+    /// phase active as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// - If the timer is stopped, the iterator is empty.
+    ///
+    /// - If the sequence repeats forever, the iterator ends once an instant is
+    ///   not representable.
+    #[must_use]
+    pub fn boundaries_at(&self, anchor: I) -> PhaseBoundaries<'a, I> {
+        let elapsed = self.sw.elapsed_at(anchor);
+        let next = match (self.sw.start, self.phase_of(elapsed)) {
+            (Some(start), Some(phase)) => elapsed
+                .checked_add(phase.remaining)
+                .map(|end| (start, end, phase.cycle, phase.index)),
+            _ => None,
+        };
+        PhaseBoundaries {
+            phases: self.phases,
+            cycles: self.cycles,
+            base: self.sw.elapsed,
+            next,
+        }
+    }
+}
+
+// private methods
+impl<I: Instant> PhaseTimer<'_, I> {
+    /// Returns the phase active after `elapsed` of the sequence.
+    fn phase_of(&self, elapsed: Duration) -> Option<Phase> {
+        let nanos = elapsed.as_nanos();
+        let cycle = u64::try_from(nanos / self.cycle.as_nanos()).ok()?;
+        if self.cycles.map_or(false, |cycles| cycle >= cycles) {
+            return None;
+        }
+
+        let mut within =
+            duration_from_nanos(nanos % self.cycle.as_nanos()).unwrap_or(Duration::MAX);
+        for (index, &phase) in self.phases.iter().enumerate() {
+            match phase.checked_sub(within) {
+                Some(remaining) if !remaining.is_zero() => {
+                    return Some(Phase {
+                        cycle,
+                        index,
+                        elapsed: within,
+                        remaining,
+                    });
+                }
+                _ => within = within.saturating_sub(phase),
+            }
+        }
+        // only reachable if the cycle duration saturated
+        None
+    }
+}
+
+/// An iterator over the upcoming ends of the phases of a [`PhaseTimer`].
+///
+/// This is returned by [`PhaseTimer::boundaries`].
+#[derive(Clone, Debug)]
+pub struct PhaseBoundaries<'a, I: Instant> {
+    phases: &'a [Duration],
+    cycles: Option<u64>,
+    /// The elapsed time of the timer's stopwatch before its last start.
+    base: Duration,
+    /// The start of the timer's stopwatch, the elapsed time at the end of
+    /// the next phase, and its position.
+    next: Option<(I, Duration, u64, usize)>,
+}
+
+impl<I: Instant> Iterator for PhaseBoundaries<'_, I> {
+    type Item = PhaseBoundary<I>;
+
+    fn next(&mut self) -> Option<PhaseBoundary<I>> {
+        let (start, end, cycle, index) = self.next.take()?;
+        let instant = start.checked_add(end.saturating_sub(self.base))?;
+
+        let (next_cycle, next_index) = if index + 1 < self.phases.len() {
+            (cycle, index + 1)
+        } else {
+            (cycle.saturating_add(1), 0)
+        };
+        if self.cycles.map_or(true, |cycles| next_cycle < cycles) {
+            self.next = end
+                .checked_add(self.phases[next_index])
+                .map(|end| (start, end, next_cycle, next_index));
+        }
+
+        Some(PhaseBoundary {
+            instant,
+            cycle,
+            index,
+        })
+    }
+}
+
+impl<I: Instant> FusedIterator for PhaseBoundaries<'_, I> {}
@@ -0,0 +1,114 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate std;
+
+use core::time::Duration;
+
+use crate::Instant;
+
+/// The number of readings taken by [`probe`].
+const SAMPLES: u32 = 10_000;
+
+/// The observed behavior of an [`Instant`] type, as measured by [`probe`].
+///
+/// Measurements are empirical, so they vary between runs and machines. They
+/// are best used to compare clocks on the same machine, or to assert
+/// properties which must hold for every sample.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::probe;
+/// # use std::time::Instant;
+/// let report = probe::<Instant>();
+/// assert!(report.is_monotonic());
+/// println!("now() takes about {:?}", report.cost);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ProbeReport {
+    /// The number of readings taken for each measurement.
+    pub samples: u32,
+    /// The average time taken by a call to [`Instant::now`], as measured by
+    /// [`std::time::Instant`].
+    pub cost: Duration,
+    /// The smallest non-zero step between consecutive readings, or [`None`]
+    /// if the clock never moved forward.
+    pub resolution: Option<Duration>,
+    /// The number of readings equal to the reading before them.
+    pub repeats: u32,
+    /// The number of readings earlier than the reading before them.
+    pub backward_steps: u32,
+}
+
+impl ProbeReport {
+    /// Returns `true` if no backward steps were observed.
+    #[must_use]
+    pub const fn is_monotonic(&self) -> bool {
+        self.backward_steps == 0
+    }
+}
+
+/// Measures the cost, resolution and monotonicity of the clock of `I`, with
+/// 10,000 readings.
+///
+/// See [`probe_with`] for details.
+#[must_use]
+pub fn probe<I: Instant>() -> ProbeReport {
+    probe_with::<I>(SAMPLES)
+}
+
+/// Measures the cost, resolution and monotonicity of the clock of `I`, with
+/// the given number of readings.
+///
+/// Readings are taken back to back, so coarse clocks are mostly observed as
+/// repeats, and their resolution only once they tick.
+///
+/// # Panics
+///
+/// Panics if `samples` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::probe_with;
+/// # use std::time::Instant;
+/// let report = probe_with::<Instant>(100);
+/// assert_eq!(report.samples, 100);
+/// ```
+#[must_use]
+#[track_caller]
+pub fn probe_with<I: Instant>(samples: u32) -> ProbeReport {
+    assert!(samples >= 2, "probe needs at least two samples");
+
+    let mut report = ProbeReport {
+        samples,
+        cost: Duration::ZERO,
+        resolution: None,
+        repeats: 0,
+        backward_steps: 0,
+    };
+
+    let timer = std::time::Instant::now();
+    for _ in 0..samples {
+        let _ = I::now();
+    }
+    report.cost = timer.elapsed() / samples;
+
+    let mut prev = I::now();
+    for _ in 1..samples {
+        let next = I::now();
+        let forward = next.saturating_duration_since(prev);
+        if !forward.is_zero() {
+            report.resolution = Some(report.resolution.map_or(forward, |min| min.min(forward)));
+        } else if prev.saturating_duration_since(next).is_zero() {
+            report.repeats += 1;
+        } else {
+            report.backward_steps += 1;
+        }
+        prev = next;
+    }
+
+    report
+}
@@ -0,0 +1,298 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate std;
+
+use core::iter::FusedIterator;
+use core::slice;
+use core::time::Duration;
+use std::vec::Vec;
+
+use crate::{Instant, Stopwatch};
+
+/// A hierarchical profiler which measures nested spans with [`Stopwatch`]es.
+///
+/// Spans are entered and exited in stack order. Each active span is backed by
+/// its own stopwatch, and when it exits, its elapsed time is accumulated into
+/// a node of a call tree. Repeated entries of the same path (as in, the same
+/// sequence of span names from the root) share a node.
+///
+/// Each node reports its number of calls, its inclusive time (including the
+/// time spent in its children), and its exclusive time (excluding it).
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::Profiler;
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let mut profiler = Profiler::new();
+/// let t0 = Instant::now();
+/// let at = |secs| t0 + Duration::from_secs(secs);
+///
+/// profiler.enter_at("compile", at(0));
+/// profiler.enter_at("parse", at(0));
+/// profiler.exit_at(at(1));
+/// profiler.enter_at("codegen", at(1));
+/// profiler.exit_at(at(4));
+/// profiler.exit_at(at(5));
+///
+/// let compile = profiler.find(&["compile"]).unwrap();
+/// assert_eq!(compile.inclusive(), Duration::from_secs(5));
+/// assert_eq!(compile.exclusive(), Duration::from_secs(1));
+///
+/// let codegen = profiler.find(&["compile", "codegen"]).unwrap();
+/// assert_eq!(codegen.calls(), 1);
+/// assert_eq!(codegen.inclusive(), Duration::from_secs(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Profiler<I: Instant> {
+    nodes: Vec<Node>,
+    roots: Vec<usize>,
+    active: Vec<Active<I>>,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    name: &'static str,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    calls: u64,
+    inclusive: Duration,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Active<I: Instant> {
+    node: usize,
+    sw: Stopwatch<I>,
+}
+
+impl<I: Instant> Profiler<I> {
+    /// Returns a profiler with no recorded or active spans.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            active: Vec::new(),
+        }
+    }
+
+    /// Enters a span named `name`, nested in the innermost active span.
+    pub fn enter(&mut self, name: &'static str) {
+        self.enter_at(name, I::now());
+    }
+
+    /// Enters a span named `name`, nested in the innermost active span, as if
+    /// the current time were `anchor`.
+    pub fn enter_at(&mut self, name: &'static str, anchor: I) {
+        let parent = self.active.last().map(|active| active.node);
+        let node = self.child(parent, name);
+        self.active.push(Active {
+            node,
+            sw: Stopwatch::new_started_at(anchor),
+        });
+    }
+
+    /// Exits the innermost active span, returning its elapsed time. If no span
+    /// is active, returns [`None`].
+    pub fn exit(&mut self) -> Option<Duration> {
+        self.exit_at(I::now())
+    }
+
+    /// Exits the innermost active span as if the current time were `anchor`,
+    /// returning its elapsed time. If no span is active, returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    pub fn exit_at(&mut self, anchor: I) -> Option<Duration> {
+        let mut active = self.active.pop()?;
+        active.sw.stop_at(anchor);
+
+        let node = &mut self.nodes[active.node];
+        node.calls = node.calls.saturating_add(1);
+        node.inclusive = node.inclusive.saturating_add(active.sw.elapsed);
+
+        Some(active.sw.elapsed)
+    }
+
+    /// Returns the number of active spans.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Returns `true` if no span is active.
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Discards all recorded and active spans.
+    pub fn reset(&mut self) {
+        self.nodes.clear();
+        self.roots.clear();
+        self.active.clear();
+    }
+
+    /// Returns an iterator over the top-level nodes of the call tree.
+    #[must_use]
+    pub fn roots(&self) -> Nodes<'_> {
+        Nodes {
+            nodes: &self.nodes,
+            indices: self.roots.iter(),
+        }
+    }
+
+    /// Returns the node reached by following `path` from the top level of the
+    /// call tree, if it exists.
+    #[must_use]
+    pub fn find(&self, path: &[&str]) -> Option<ProfileNode<'_>> {
+        let mut siblings = &self.roots;
+        let mut found = None;
+        for name in path {
+            let index = self.find_in(siblings, name)?;
+            siblings = &self.nodes[index].children;
+            found = Some(index);
+        }
+        found.map(|index| ProfileNode {
+            nodes: &self.nodes,
+            index,
+        })
+    }
+}
+
+// private methods
+impl<I: Instant> Profiler<I> {
+    fn find_in(&self, siblings: &[usize], name: &str) -> Option<usize> {
+        siblings
+            .iter()
+            .copied()
+            .find(|&index| self.nodes[index].name == name)
+    }
+
+    /// Returns the index of the node named `name` under `parent`, creating it
+    /// if it doesn't exist.
+    fn child(&mut self, parent: Option<usize>, name: &'static str) -> usize {
+        let siblings = match parent {
+            Some(parent) => &self.nodes[parent].children,
+            None => &self.roots,
+        };
+        if let Some(index) = self.find_in(siblings, name) {
+            return index;
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            name,
+            parent,
+            children: Vec::new(),
+            calls: 0,
+            inclusive: Duration::ZERO,
+        });
+        match parent {
+            Some(parent) => self.nodes[parent].children.push(index),
+            None => self.roots.push(index),
+        }
+        index
+    }
+}
+
+impl<I: Instant> Default for Profiler<I> {
+    /// Returns the default profiler. Same as calling [`Profiler::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A node of a [`Profiler`]'s call tree.
+#[derive(Clone, Copy, Debug)]
+pub struct ProfileNode<'a> {
+    nodes: &'a [Node],
+    index: usize,
+}
+
+impl<'a> ProfileNode<'a> {
+    /// Returns the name of the span.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.node().name
+    }
+
+    /// Returns the number of times the span was exited.
+    #[must_use]
+    pub fn calls(&self) -> u64 {
+        self.node().calls
+    }
+
+    /// Returns the total time spent in the span, including time spent in its
+    /// children. If overflow occurred, this is saturated to [`Duration::MAX`].
+    #[must_use]
+    pub fn inclusive(&self) -> Duration {
+        self.node().inclusive
+    }
+
+    /// Returns the total time spent in the span, excluding time spent in its
+    /// children.
+    #[must_use]
+    pub fn exclusive(&self) -> Duration {
+        let children = self.children().fold(Duration::ZERO, |sum, child| {
+            sum.saturating_add(child.inclusive())
+        });
+        self.inclusive().saturating_sub(children)
+    }
+
+    /// Returns the node this span is nested in, if any.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        self.node().parent.map(|index| Self {
+            nodes: self.nodes,
+            index,
+        })
+    }
+
+    /// Returns an iterator over the spans nested in this span.
+    #[must_use]
+    pub fn children(&self) -> Nodes<'a> {
+        Nodes {
+            nodes: self.nodes,
+            indices: self.nodes[self.index].children.iter(),
+        }
+    }
+
+    fn node(&self) -> &'a Node {
+        &self.nodes[self.index]
+    }
+}
+
+/// An iterator over [`ProfileNode`]s.
+///
+/// This is returned by [`Profiler::roots`] and [`ProfileNode::children`].
+#[derive(Clone, Debug)]
+pub struct Nodes<'a> {
+    nodes: &'a [Node],
+    indices: slice::Iter<'a, usize>,
+}
+
+impl<'a> Iterator for Nodes<'a> {
+    type Item = ProfileNode<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = *self.indices.next()?;
+        Some(ProfileNode {
+            nodes: self.nodes,
+            index,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Nodes<'_> {}
+
+impl FusedIterator for Nodes<'_> {}
@@ -0,0 +1,552 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops;
+use core::time::Duration;
+
+use crate::canonical::{furthest_shift, Shape};
+use crate::{Instant, Stopwatch};
+
+/// A span of time which may be negative, such as a time penalty.
+///
+/// A signed duration is a [`Duration`] magnitude and a sign. Zero is never
+/// negative, so each value has exactly one representation.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::SignedDuration;
+/// # use core::time::Duration;
+/// let debt = SignedDuration::negative(Duration::from_secs(30));
+/// let sum = debt.saturating_add(SignedDuration::positive(Duration::from_secs(10)));
+/// assert!(sum.is_negative());
+/// assert_eq!(sum.magnitude(), Duration::from_secs(20));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SignedDuration {
+    negative: bool,
+    magnitude: Duration,
+}
+
+impl SignedDuration {
+    /// A duration of zero time.
+    pub const ZERO: Self = Self::positive(Duration::ZERO);
+
+    /// The largest signed duration, [`Duration::MAX`].
+    pub const MAX: Self = Self::positive(Duration::MAX);
+
+    /// The smallest signed duration, the negation of [`Duration::MAX`].
+    pub const MIN: Self = Self::negative(Duration::MAX);
+
+    /// Returns a non-negative duration of `magnitude`.
+    #[must_use]
+    pub const fn positive(magnitude: Duration) -> Self {
+        Self {
+            negative: false,
+            magnitude,
+        }
+    }
+
+    /// Returns a duration of `magnitude` below zero. If `magnitude` is zero,
+    /// returns [`SignedDuration::ZERO`].
+    #[must_use]
+    pub const fn negative(magnitude: Duration) -> Self {
+        Self {
+            negative: !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    /// Returns the absolute value of the duration.
+    #[must_use]
+    pub const fn magnitude(&self) -> Duration {
+        self.magnitude
+    }
+
+    /// Returns `true` if the duration is below zero.
+    #[must_use]
+    pub const fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns `true` if the duration is above zero.
+    #[must_use]
+    pub const fn is_positive(&self) -> bool {
+        !self.negative && !self.magnitude.is_zero()
+    }
+
+    /// Returns `true` if the duration is zero.
+    #[must_use]
+    pub const fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    /// Adds `rhs` to `self`, returning [`None`] if the magnitude of the result
+    /// would exceed [`Duration::MAX`].
+    #[must_use]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        if self.negative == rhs.negative {
+            let magnitude = self.magnitude.checked_add(rhs.magnitude)?;
+            Some(Self::with_sign(self.negative, magnitude))
+        } else if self.magnitude >= rhs.magnitude {
+            Some(Self::with_sign(
+                self.negative,
+                self.magnitude.saturating_sub(rhs.magnitude),
+            ))
+        } else {
+            Some(Self::with_sign(
+                rhs.negative,
+                rhs.magnitude.saturating_sub(self.magnitude),
+            ))
+        }
+    }
+
+    /// Subtracts `rhs` from `self`, returning [`None`] if the magnitude of the
+    /// result would exceed [`Duration::MAX`].
+    #[must_use]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(-rhs)
+    }
+
+    /// Adds `rhs` to `self`, saturating to [`SignedDuration::MAX`] or
+    /// [`SignedDuration::MIN`] if overflow occurs.
+    #[must_use]
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs)
+            .unwrap_or(if self.negative { Self::MIN } else { Self::MAX })
+    }
+
+    /// Subtracts `rhs` from `self`, saturating to [`SignedDuration::MAX`] or
+    /// [`SignedDuration::MIN`] if overflow occurs.
+    #[must_use]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.saturating_add(-rhs)
+    }
+}
+
+// private methods
+impl SignedDuration {
+    /// Returns a duration of `magnitude` with the given sign.
+    const fn with_sign(negative: bool, magnitude: Duration) -> Self {
+        if negative {
+            Self::negative(magnitude)
+        } else {
+            Self::positive(magnitude)
+        }
+    }
+}
+
+impl Default for SignedDuration {
+    /// Returns the default signed duration. Same as
+    /// [`SignedDuration::ZERO`].
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl ops::Neg for SignedDuration {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::with_sign(!self.negative, self.magnitude)
+    }
+}
+
+impl PartialOrd for SignedDuration {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl Ord for SignedDuration {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        match (self.negative, rhs.negative) {
+            (false, false) => self.magnitude.cmp(&rhs.magnitude),
+            (true, true) => rhs.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl From<Duration> for SignedDuration {
+    fn from(dur: Duration) -> Self {
+        Self::positive(dur)
+    }
+}
+
+impl TryFrom<SignedDuration> for Duration {
+    type Error = SignedDuration;
+
+    /// Returns the magnitude of `dur` if it is not negative. Otherwise,
+    /// returns `dur` as the error.
+    fn try_from(dur: SignedDuration) -> Result<Self, SignedDuration> {
+        if dur.negative {
+            Err(dur)
+        } else {
+            Ok(dur.magnitude)
+        }
+    }
+}
+
+/// A stopwatch whose elapsed time may be negative, such as when starting
+/// with a time penalty.
+///
+/// Starting, stopping and measuring behave as with [`Stopwatch`], except the
+/// time elapsed is a [`SignedDuration`]. A running stopwatch with negative
+/// elapsed time counts up towards zero, and then past it.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{SignedDuration, SignedStopwatch};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let start = Instant::now();
+/// let handicap = SignedDuration::negative(Duration::from_secs(30));
+/// let mut sw = SignedStopwatch::<Instant>::with_elapsed(handicap);
+/// sw.start_at(start);
+///
+/// let elapsed = sw.elapsed_at(start + Duration::from_secs(10));
+/// assert_eq!(elapsed, SignedDuration::negative(Duration::from_secs(20)));
+///
+/// let elapsed = sw.elapsed_at(start + Duration::from_secs(45));
+/// assert_eq!(elapsed, SignedDuration::positive(Duration::from_secs(15)));
+/// ```
+///
+/// # Notes
+///
+/// As with [`Stopwatch`], stopwatches whose fields differ are equal according
+/// to [`PartialEq`], [`Eq`], and [`Hash`] if they measure the same elapsed
+/// time at every instant.
+#[derive(Clone, Copy, Debug)]
+pub struct SignedStopwatch<I: Instant> {
+    /// Accumulated elapsed time.
+    pub elapsed: SignedDuration,
+    /// The instant at which the stopwatch was started, if it is running.
+    /// Otherwise, [`None`].
+    pub start: Option<I>,
+}
+
+impl<I: Instant> SignedStopwatch<I> {
+    /// Returns a stopped stopwatch with zero elapsed time.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_elapsed(SignedDuration::ZERO)
+    }
+
+    /// Returns a running stopwatch initialized with zero elapsed time.
+    #[must_use]
+    pub fn new_started() -> Self {
+        Self::new_started_at(I::now())
+    }
+
+    /// Returns a stopwatch initialized with zero elapsed time, started at the
+    /// given instant.
+    #[must_use]
+    pub const fn new_started_at(start: I) -> Self {
+        Self::from_raw(SignedDuration::ZERO, Some(start))
+    }
+
+    /// Returns a stopped stopwatch with the given elapsed time.
+    #[must_use]
+    pub const fn with_elapsed(elapsed: SignedDuration) -> Self {
+        Self::from_raw(elapsed, None)
+    }
+
+    /// Returns a running stopwatch initialized with the given elapsed time.
+    #[must_use]
+    pub fn with_elapsed_started(elapsed: SignedDuration) -> Self {
+        Self::from_raw(elapsed, Some(I::now()))
+    }
+
+    /// Returns a stopwatch from its raw parts.
+    #[must_use]
+    pub const fn from_raw(elapsed: SignedDuration, start: Option<I>) -> Self {
+        Self { elapsed, start }
+    }
+
+    /// Returns `true` if the stopwatch is running.
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        self.start.is_some()
+    }
+
+    /// Returns `true` if the stopwatch is stopped.
+    #[must_use]
+    pub const fn is_stopped(&self) -> bool {
+        !self.is_running()
+    }
+
+    /// Returns the total time elapsed. If overflow occurs, the elapsed time is
+    /// saturated to [`SignedDuration::MAX`].
+    #[must_use]
+    pub fn elapsed(&self) -> SignedDuration {
+        self.elapsed_at(I::now())
+    }
+
+    /// Returns the total time elapsed, measured as if the current time were
+    /// `anchor`. If overflow occurs, the elapsed time is saturated to
+    /// [`SignedDuration::MAX`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`.
+    #[must_use]
+    pub fn elapsed_at(&self, anchor: I) -> SignedDuration {
+        self.checked_elapsed_at(anchor)
+            .unwrap_or(SignedDuration::MAX)
+    }
+
+    /// Computes the total time elapsed. If overflow occurs, returns [`None`].
+    #[must_use]
+    pub fn checked_elapsed(&self) -> Option<SignedDuration> {
+        self.checked_elapsed_at(I::now())
+    }
+
+    /// Computes the total time elapsed, measured as if the current time were
+    /// `anchor`. If overflow occurs, returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`.
+    #[must_use]
+    pub fn checked_elapsed_at(&self, anchor: I) -> Option<SignedDuration> {
+        if let Some(start) = self.start {
+            let after_start = anchor.saturating_duration_since(start);
+            self.elapsed
+                .checked_add(SignedDuration::positive(after_start))
+        } else {
+            Some(self.elapsed)
+        }
+    }
+
+    /// Starts measuring the time elapsed.
+    pub fn start(&mut self) {
+        self.start_at(I::now());
+    }
+
+    /// Starts measuring the time elapsed as if the current time were `anchor`.
+    /// If the stopwatch is already running, the prior start time is
+    /// overwritten.
+    pub fn start_at(&mut self, anchor: I) {
+        self.start = Some(anchor);
+    }
+
+    /// Stops measuring the time elapsed since the last start.
+    pub fn stop(&mut self) {
+        self.stop_at(I::now());
+    }
+
+    /// Stops measuring the time elapsed since the last start as if the
+    /// current time were `anchor`. If overflow occurs, the elapsed time is
+    /// saturated to [`SignedDuration::MAX`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`.
+    pub fn stop_at(&mut self, anchor: I) {
+        if self.is_running() {
+            self.elapsed = self.elapsed_at(anchor);
+            self.start = None;
+        }
+    }
+
+    /// Tries to stop the stopwatch. If overflow occurs, returns `false` and
+    /// leaves the stopwatch unchanged.
+    #[must_use]
+    pub fn checked_stop(&mut self) -> bool {
+        self.checked_stop_at(I::now())
+    }
+
+    /// Tries to stop the stopwatch, as if the current time were `anchor`. If
+    /// overflow occurs, returns `false` and leaves the stopwatch unchanged.
+    #[must_use]
+    pub fn checked_stop_at(&mut self, anchor: I) -> bool {
+        if let Some(elapsed) = self.checked_elapsed_at(anchor) {
+            self.elapsed = elapsed;
+            self.start = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Toggles whether the stopwatch is running or stopped.
+    pub fn toggle(&mut self) {
+        self.toggle_at(I::now());
+    }
+
+    /// Toggles whether the stopwatch is running or stopped, as if the current
+    /// time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`start_at`](Self::start_at) and [`stop_at`](Self::stop_at) for
+    /// notes about the chronology of `anchor`, as well as what happens if
+    /// overflow occurs.
+    pub fn toggle_at(&mut self, anchor: I) {
+        if self.is_running() {
+            self.stop_at(anchor);
+        } else {
+            self.start_at(anchor);
+        }
+    }
+
+    /// Stops and resets the elapsed time to zero.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Resets the elapsed time to zero without affecting whether the
+    /// stopwatch is running.
+    pub fn reset_in_place(&mut self) {
+        self.reset_in_place_at(I::now());
+    }
+
+    /// Resets the elapsed time to zero without affecting whether the
+    /// stopwatch is running, as if the current time were `start`.
+    pub fn reset_in_place_at(&mut self, start: I) {
+        if self.is_running() {
+            *self = Self::new_started_at(start);
+        } else {
+            self.reset();
+        }
+    }
+
+    /// Adds `dur` to the elapsed time, saturating to [`SignedDuration::MAX`]
+    /// or [`SignedDuration::MIN`] if overflow occurs. The time elapsed since
+    /// the last start is not affected.
+    #[must_use]
+    pub fn saturating_add(mut self, dur: SignedDuration) -> Self {
+        self.elapsed = self.elapsed.saturating_add(dur);
+        self
+    }
+
+    /// Subtracts `dur` from the elapsed time, saturating to
+    /// [`SignedDuration::MAX`] or [`SignedDuration::MIN`] if overflow occurs.
+    /// Unlike [`Stopwatch::saturating_sub`], the result may be negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::{SignedDuration, SignedStopwatch};
+    /// # use core::time::Duration;
+    /// let sw = SignedStopwatch::<std::time::Instant>::new();
+    /// let penalty = SignedDuration::positive(Duration::from_secs(30));
+    /// assert!(sw.saturating_sub(penalty).elapsed().is_negative());
+    /// ```
+    #[must_use]
+    pub fn saturating_sub(mut self, dur: SignedDuration) -> Self {
+        self.elapsed = self.elapsed.saturating_sub(dur);
+        self
+    }
+
+    /// Adds `dur` to the elapsed time. If overflow occurs, returns [`None`].
+    #[must_use]
+    pub fn checked_add(mut self, dur: SignedDuration) -> Option<Self> {
+        self.elapsed = self.elapsed.checked_add(dur)?;
+        Some(self)
+    }
+
+    /// Subtracts `dur` from the elapsed time. If overflow occurs, returns
+    /// [`None`].
+    #[must_use]
+    pub fn checked_sub(mut self, dur: SignedDuration) -> Option<Self> {
+        self.elapsed = self.elapsed.checked_sub(dur)?;
+        Some(self)
+    }
+}
+
+// private methods
+impl<I: Instant> SignedStopwatch<I> {
+    /// Returns the canonical shape of the stopwatch, as with
+    /// [`Stopwatch::canonical`]. If running, the elapsed time is moved into
+    /// the start, backwards if positive and forwards if negative.
+    fn canonical(&self) -> Shape<I, SignedDuration> {
+        match self.start {
+            None => Shape::Stopped(self.elapsed),
+
+            Some(start) => {
+                let SignedDuration {
+                    negative,
+                    magnitude,
+                } = self.elapsed;
+                let shift: fn(&I, Duration) -> Option<I> = if negative {
+                    I::checked_add
+                } else {
+                    I::checked_sub
+                };
+                if let Some(zero) = shift(&start, magnitude) {
+                    Shape::Bounded(zero)
+                } else {
+                    let (start, moved) = furthest_shift(start, magnitude, shift);
+                    Shape::Unbounded {
+                        start,
+                        excess: SignedDuration::with_sign(
+                            negative,
+                            magnitude.saturating_sub(moved),
+                        ),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<I: Instant> PartialEq for SignedStopwatch<I> {
+    /// Tests for equality between `self` and `rhs`.
+    ///
+    /// Stopwatches are equal if whether they are running and their elapsed time
+    /// are equal, following the same rules as [`Stopwatch`].
+    fn eq(&self, rhs: &Self) -> bool {
+        self.canonical() == rhs.canonical()
+    }
+}
+
+impl<I: Instant> Eq for SignedStopwatch<I> {}
+
+impl<I: Instant + Hash> Hash for SignedStopwatch<I> {
+    /// Hashes `self` and `rhs`. These hashes are not dependent on the time of
+    /// measurement, so they can be used to test equality.
+    ///
+    /// # Support
+    ///
+    /// `I` (the [`Instant`] type used by the stopwatch) must implement
+    /// [`Hash`].
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state);
+    }
+}
+
+impl<I: Instant> Default for SignedStopwatch<I> {
+    /// Returns the default stopwatch. Same as calling
+    /// [`SignedStopwatch::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Instant> From<Stopwatch<I>> for SignedStopwatch<I> {
+    fn from(sw: Stopwatch<I>) -> Self {
+        Self::from_raw(SignedDuration::positive(sw.elapsed), sw.start)
+    }
+}
+
+impl<I: Instant> TryFrom<SignedStopwatch<I>> for Stopwatch<I> {
+    type Error = SignedStopwatch<I>;
+
+    /// Converts `sw` to a [`Stopwatch`] if its accumulated elapsed time is not
+    /// negative. Otherwise, returns `sw` as the error.
+    fn try_from(sw: SignedStopwatch<I>) -> Result<Self, SignedStopwatch<I>> {
+        match Duration::try_from(sw.elapsed) {
+            Ok(elapsed) => Ok(Self::from_raw(elapsed, sw.start)),
+            Err(_) => Err(sw),
+        }
+    }
+}
@@ -0,0 +1,507 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::borrow::{Borrow, BorrowMut};
+use core::iter::FusedIterator;
+use core::slice;
+use core::time::Duration;
+
+use crate::{Instant, Stopwatch};
+
+/// Operations on many stopwatches at once, reading the clock only once.
+///
+/// Each method applies the [`Stopwatch`] method of the same name to every
+/// stopwatch, with the same anchor. This is implemented for slices, so it
+/// also works with arrays and vectors. It is sealed, and cannot be
+/// implemented outside of this crate.
+///
+/// To operate on some stopwatches of a collection, or on stopwatches which
+/// are not stored in a slice, see [`StopwatchIterExt`].
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{StopwatchSliceExt, Sw};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let mut stopwatches = [Sw::new(); 3];
+/// let start = Instant::now();
+/// stopwatches.start_all_at(start);
+///
+/// let anchor = start + Duration::from_secs(1);
+/// assert!(stopwatches.elapsed_all_at(anchor).all(|elapsed| elapsed == Duration::from_secs(1)));
+/// ```
+pub trait StopwatchSliceExt<I: Instant>: sealed::Sealed {
+    /// Starts every stopwatch at now.
+    fn start_all(&mut self) {
+        self.start_all_at(I::now());
+    }
+
+    /// Starts every stopwatch as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::start_at`] for notes about the chronology of
+    /// `anchor`.
+    fn start_all_at(&mut self, anchor: I);
+
+    /// Stops every stopwatch at now.
+    fn stop_all(&mut self) {
+        self.stop_all_at(I::now());
+    }
+
+    /// Stops every stopwatch as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    fn stop_all_at(&mut self, anchor: I);
+
+    /// Tries to stop every stopwatch at now. See
+    /// [`checked_stop_all_at`](Self::checked_stop_all_at) for details.
+    #[must_use]
+    fn checked_stop_all<F: FnMut(usize)>(&mut self, overflowed: F) -> bool {
+        self.checked_stop_all_at(I::now(), overflowed)
+    }
+
+    /// Tries to stop every stopwatch as if the current time were `anchor`.
+    ///
+    /// Stopwatches which would overflow are left unchanged, and their index
+    /// is passed to `overflowed`. Returns `true` if none overflowed.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::checked_stop_at`] for notes about the chronology of
+    /// `anchor`.
+    #[must_use]
+    fn checked_stop_all_at<F: FnMut(usize)>(&mut self, anchor: I, overflowed: F) -> bool;
+
+    /// Toggles every stopwatch at now.
+    fn toggle_all(&mut self) {
+        self.toggle_all_at(I::now());
+    }
+
+    /// Toggles every stopwatch as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::toggle_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    fn toggle_all_at(&mut self, anchor: I);
+
+    /// Tries to toggle every stopwatch at now. See
+    /// [`checked_toggle_all_at`](Self::checked_toggle_all_at) for details.
+    #[must_use]
+    fn checked_toggle_all<F: FnMut(usize)>(&mut self, overflowed: F) -> bool {
+        self.checked_toggle_all_at(I::now(), overflowed)
+    }
+
+    /// Tries to toggle every stopwatch as if the current time were `anchor`.
+    ///
+    /// Stopwatches which would overflow are left unchanged, and their index
+    /// is passed to `overflowed`. Returns `true` if none overflowed.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::checked_toggle_at`] for notes about the chronology of
+    /// `anchor`.
+    #[must_use]
+    fn checked_toggle_all_at<F: FnMut(usize)>(&mut self, anchor: I, overflowed: F) -> bool;
+
+    /// Stops and resets every stopwatch.
+    fn reset_all(&mut self);
+
+    /// Resets the elapsed time of every stopwatch at now, without affecting
+    /// whether they are running.
+    fn reset_in_place_all(&mut self) {
+        self.reset_in_place_all_at(I::now());
+    }
+
+    /// Resets the elapsed time of every stopwatch as if the current time were
+    /// `start`, without affecting whether they are running.
+    fn reset_in_place_all_at(&mut self, start: I);
+
+    /// Returns an iterator over the time elapsed of every stopwatch at now.
+    fn elapsed_all(&self) -> ElapsedAll<'_, I> {
+        self.elapsed_all_at(I::now())
+    }
+
+    /// Returns an iterator over the time elapsed of every stopwatch, measured
+    /// as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    fn elapsed_all_at(&self, anchor: I) -> ElapsedAll<'_, I>;
+}
+
+impl<I: Instant> StopwatchSliceExt<I> for [Stopwatch<I>] {
+    fn start_all_at(&mut self, anchor: I) {
+        for sw in self {
+            sw.start_at(anchor);
+        }
+    }
+
+    fn stop_all_at(&mut self, anchor: I) {
+        for sw in self {
+            sw.stop_at(anchor);
+        }
+    }
+
+    fn checked_stop_all_at<F: FnMut(usize)>(&mut self, anchor: I, mut overflowed: F) -> bool {
+        let mut ok = true;
+        for (index, sw) in self.iter_mut().enumerate() {
+            if !sw.checked_stop_at(anchor) {
+                overflowed(index);
+                ok = false;
+            }
+        }
+        ok
+    }
+
+    fn toggle_all_at(&mut self, anchor: I) {
+        for sw in self {
+            sw.toggle_at(anchor);
+        }
+    }
+
+    fn checked_toggle_all_at<F: FnMut(usize)>(&mut self, anchor: I, mut overflowed: F) -> bool {
+        let mut ok = true;
+        for (index, sw) in self.iter_mut().enumerate() {
+            if !sw.checked_toggle_at(anchor) {
+                overflowed(index);
+                ok = false;
+            }
+        }
+        ok
+    }
+
+    fn reset_all(&mut self) {
+        for sw in self {
+            sw.reset();
+        }
+    }
+
+    fn reset_in_place_all_at(&mut self, start: I) {
+        for sw in self {
+            sw.reset_in_place_at(start);
+        }
+    }
+
+    fn elapsed_all_at(&self, anchor: I) -> ElapsedAll<'_, I> {
+        ElapsedAll {
+            inner: self.iter(),
+            anchor,
+        }
+    }
+}
+
+/// Operations on every stopwatch yielded by an iterator, reading the clock
+/// only once.
+///
+/// This is implemented for every iterator over stopwatches, references to
+/// stopwatches, or mutable references to stopwatches. Methods which modify
+/// the stopwatches require mutable references. As with
+/// [`StopwatchSliceExt`], each method applies the [`Stopwatch`] method of the
+/// same name with the same anchor. It is sealed, and cannot be implemented
+/// outside of this crate.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{StopwatchIterExt, Sw};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let mut stopwatches = [Sw::new(); 4];
+/// let start = Instant::now();
+/// stopwatches.iter_mut().step_by(2).start_all_at(start);
+///
+/// let anchor = start + Duration::from_secs(1);
+/// assert_eq!(stopwatches.iter().latest_start(), Some(start));
+/// assert_eq!(
+///     stopwatches.iter().checked_sum_elapsed_at(anchor),
+///     Some(Duration::from_secs(2)),
+/// );
+/// ```
+pub trait StopwatchIterExt<I: Instant>: Iterator + Sized + sealed::SealedIter<I>
+where
+    Self::Item: Borrow<Stopwatch<I>>,
+{
+    /// Starts every stopwatch at now.
+    fn start_all(self)
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        self.start_all_at(I::now());
+    }
+
+    /// Starts every stopwatch as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::start_at`] for notes about the chronology of
+    /// `anchor`.
+    fn start_all_at(self, anchor: I)
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        for mut sw in self {
+            sw.borrow_mut().start_at(anchor);
+        }
+    }
+
+    /// Stops every stopwatch at now.
+    fn stop_all(self)
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        self.stop_all_at(I::now());
+    }
+
+    /// Stops every stopwatch as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    fn stop_all_at(self, anchor: I)
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        for mut sw in self {
+            sw.borrow_mut().stop_at(anchor);
+        }
+    }
+
+    /// Tries to stop every stopwatch at now. See
+    /// [`checked_stop_all_at`](Self::checked_stop_all_at) for details.
+    #[must_use]
+    fn checked_stop_all<F: FnMut(usize)>(self, overflowed: F) -> bool
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        self.checked_stop_all_at(I::now(), overflowed)
+    }
+
+    /// Tries to stop every stopwatch as if the current time were `anchor`.
+    ///
+    /// Stopwatches which would overflow are left unchanged, and their
+    /// position in the iterator is passed to `overflowed`. Returns `true` if
+    /// none overflowed.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::checked_stop_at`] for notes about the chronology of
+    /// `anchor`.
+    #[must_use]
+    fn checked_stop_all_at<F: FnMut(usize)>(self, anchor: I, mut overflowed: F) -> bool
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        let mut ok = true;
+        for (index, mut sw) in self.enumerate() {
+            if !sw.borrow_mut().checked_stop_at(anchor) {
+                overflowed(index);
+                ok = false;
+            }
+        }
+        ok
+    }
+
+    /// Toggles every stopwatch at now.
+    fn toggle_all(self)
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        self.toggle_all_at(I::now());
+    }
+
+    /// Toggles every stopwatch as if the current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::toggle_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    fn toggle_all_at(self, anchor: I)
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        for mut sw in self {
+            sw.borrow_mut().toggle_at(anchor);
+        }
+    }
+
+    /// Tries to toggle every stopwatch at now. See
+    /// [`checked_toggle_all_at`](Self::checked_toggle_all_at) for details.
+    #[must_use]
+    fn checked_toggle_all<F: FnMut(usize)>(self, overflowed: F) -> bool
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        self.checked_toggle_all_at(I::now(), overflowed)
+    }
+
+    /// Tries to toggle every stopwatch as if the current time were `anchor`.
+    ///
+    /// Stopwatches which would overflow are left unchanged, and their
+    /// position in the iterator is passed to `overflowed`. Returns `true` if
+    /// none overflowed.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::checked_toggle_at`] for notes about the chronology of
+    /// `anchor`.
+    #[must_use]
+    fn checked_toggle_all_at<F: FnMut(usize)>(self, anchor: I, mut overflowed: F) -> bool
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        let mut ok = true;
+        for (index, mut sw) in self.enumerate() {
+            if !sw.borrow_mut().checked_toggle_at(anchor) {
+                overflowed(index);
+                ok = false;
+            }
+        }
+        ok
+    }
+
+    /// Stops and resets every stopwatch.
+    fn reset_all(self)
+    where
+        Self::Item: BorrowMut<Stopwatch<I>>,
+    {
+        for mut sw in self {
+            sw.borrow_mut().reset();
+        }
+    }
+
+    /// Returns the sum of the time elapsed of every stopwatch at now. See
+    /// [`checked_sum_elapsed_at`](Self::checked_sum_elapsed_at) for details.
+    #[must_use]
+    fn checked_sum_elapsed(self) -> Option<Duration> {
+        self.checked_sum_elapsed_at(I::now())
+    }
+
+    /// Returns the sum of the time elapsed of every stopwatch, measured as if
+    /// the current time were `anchor`. If overflow occurs, returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::checked_elapsed_at`] for notes about the chronology
+    /// of `anchor`.
+    #[must_use]
+    fn checked_sum_elapsed_at(self, anchor: I) -> Option<Duration> {
+        let mut sum = Duration::ZERO;
+        for sw in self {
+            sum = sum.checked_add(sw.borrow().checked_elapsed_at(anchor)?)?;
+        }
+        Some(sum)
+    }
+
+    /// Returns the sum of the time elapsed of every stopwatch at now. See
+    /// [`saturating_sum_elapsed_at`](Self::saturating_sum_elapsed_at) for
+    /// details.
+    #[must_use]
+    fn saturating_sum_elapsed(self) -> Duration {
+        self.saturating_sum_elapsed_at(I::now())
+    }
+
+    /// Returns the sum of the time elapsed of every stopwatch, measured as if
+    /// the current time were `anchor`. If overflow occurs, the sum is
+    /// saturated to [`Duration::MAX`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`.
+    #[must_use]
+    fn saturating_sum_elapsed_at(self, anchor: I) -> Duration {
+        self.map(|sw| sw.borrow().elapsed_at(anchor))
+            .fold(Duration::ZERO, Duration::saturating_add)
+    }
+
+    /// Returns the latest start of the running stopwatches, or [`None`] if
+    /// none are running.
+    #[must_use]
+    fn latest_start(self) -> Option<I> {
+        self.filter_map(|sw| sw.borrow().start)
+            .reduce(|latest, start| {
+                if is_after(start, latest) {
+                    start
+                } else {
+                    latest
+                }
+            })
+    }
+
+    /// Returns the earliest start of the running stopwatches, or [`None`] if
+    /// none are running.
+    #[must_use]
+    fn earliest_start(self) -> Option<I> {
+        self.filter_map(|sw| sw.borrow().start)
+            .reduce(|earliest, start| {
+                if is_after(earliest, start) {
+                    start
+                } else {
+                    earliest
+                }
+            })
+    }
+}
+
+impl<I: Instant, T: Iterator> StopwatchIterExt<I> for T where T::Item: Borrow<Stopwatch<I>> {}
+
+/// Returns `true` if `lhs` is strictly after `rhs`.
+fn is_after<I: Instant>(lhs: I, rhs: I) -> bool {
+    lhs.saturating_duration_since(rhs) > Duration::ZERO
+}
+
+mod sealed {
+    use core::borrow::Borrow;
+
+    use crate::{Instant, Stopwatch};
+
+    pub trait Sealed {}
+
+    impl<I: Instant> Sealed for [Stopwatch<I>] {}
+
+    pub trait SealedIter<I> {}
+
+    impl<I: Instant, T: Iterator> SealedIter<I> for T where T::Item: Borrow<Stopwatch<I>> {}
+}
+
+/// An iterator over the time elapsed of many stopwatches, measured at the
+/// same anchor.
+///
+/// This is returned by [`StopwatchSliceExt::elapsed_all_at`].
+#[derive(Clone, Debug)]
+pub struct ElapsedAll<'a, I: Instant> {
+    inner: slice::Iter<'a, Stopwatch<I>>,
+    anchor: I,
+}
+
+impl<I: Instant> Iterator for ElapsedAll<'_, I> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        self.inner.next().map(|sw| sw.elapsed_at(self.anchor))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I: Instant> DoubleEndedIterator for ElapsedAll<'_, I> {
+    fn next_back(&mut self) -> Option<Duration> {
+        self.inner.next_back().map(|sw| sw.elapsed_at(self.anchor))
+    }
+}
+
+impl<I: Instant> ExactSizeIterator for ElapsedAll<'_, I> {}
+
+impl<I: Instant> FusedIterator for ElapsedAll<'_, I> {}
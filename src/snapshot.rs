@@ -0,0 +1,220 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::iter::FusedIterator;
+use core::ops;
+use core::time::Duration;
+
+use crate::{Instant, Stopwatch};
+
+/// The state of a [`Stopwatch`] at a point in time.
+///
+/// Subtracting an earlier snapshot from a later one gives the time
+/// accumulated in between, without resetting the stopwatch.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::Sw;
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let start = Instant::now();
+/// let sw = Sw::new_started_at(start);
+///
+/// let last_report = sw.snapshot_at(start + Duration::from_secs(10));
+/// let report = sw.snapshot_at(start + Duration::from_secs(25));
+/// assert_eq!(report - last_report, Duration::from_secs(15));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Snapshot<I: Instant> {
+    /// The total time elapsed at `anchor`, saturated to [`Duration::MAX`].
+    pub elapsed: Duration,
+    /// Whether the stopwatch was running.
+    pub running: bool,
+    /// The instant the snapshot was captured at.
+    pub anchor: I,
+}
+
+impl<I: Instant> Snapshot<I> {
+    /// Captures snapshots of every stopwatch in `stopwatches` at now.
+    #[must_use]
+    pub fn all<const N: usize>(stopwatches: &[Stopwatch<I>; N]) -> [Self; N] {
+        Self::all_at(stopwatches, I::now())
+    }
+
+    /// Captures snapshots of every stopwatch in `stopwatches` at `anchor`,
+    /// so that they are consistent with each other.
+    ///
+    /// For slices and other collections, see [`each_at`](Self::each_at).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::{Snapshot, Sw};
+    /// # use core::time::Duration;
+    /// # use std::time::Instant;
+    /// let start = Instant::now();
+    /// let stopwatches = [Sw::new_started_at(start), Sw::new()];
+    /// let snapshots = Snapshot::all_at(&stopwatches, start + Duration::from_secs(1));
+    /// assert_eq!(snapshots[0].elapsed, Duration::from_secs(1));
+    /// assert!(!snapshots[1].running);
+    /// ```
+    #[must_use]
+    pub fn all_at<const N: usize>(stopwatches: &[Stopwatch<I>; N], anchor: I) -> [Self; N] {
+        stopwatches.map(|sw| sw.snapshot_at(anchor))
+    }
+
+    /// Returns an iterator capturing snapshots of every stopwatch in
+    /// `stopwatches` at now.
+    ///
+    /// The current time is read once, when this is called.
+    pub fn each<'a, T>(stopwatches: T) -> Snapshots<T::IntoIter, I>
+    where
+        T: IntoIterator<Item = &'a Stopwatch<I>>,
+        I: 'a,
+    {
+        Self::each_at(stopwatches, I::now())
+    }
+
+    /// Returns an iterator capturing snapshots of every stopwatch in
+    /// `stopwatches` at `anchor`, so that they are consistent with each other.
+    ///
+    /// Unlike [`all_at`](Self::all_at), this accepts slices, iterators and
+    /// other collections of stopwatch references.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::{Snapshot, Sw};
+    /// # use core::time::Duration;
+    /// # use std::time::Instant;
+    /// let start = Instant::now();
+    /// let stopwatches = vec![Sw::new_started_at(start), Sw::new()];
+    /// let snapshots: Vec<_> =
+    ///     Snapshot::each_at(&stopwatches, start + Duration::from_secs(1)).collect();
+    /// assert_eq!(snapshots[0].elapsed, Duration::from_secs(1));
+    /// assert!(!snapshots[1].running);
+    /// ```
+    pub fn each_at<'a, T>(stopwatches: T, anchor: I) -> Snapshots<T::IntoIter, I>
+    where
+        T: IntoIterator<Item = &'a Stopwatch<I>>,
+        I: 'a,
+    {
+        Snapshots {
+            inner: stopwatches.into_iter(),
+            anchor,
+        }
+    }
+
+    /// Returns the time accumulated between `earlier` and `self`. If the
+    /// stopwatch was reset or had time subtracted in between, so that less
+    /// time had elapsed at `self`, or if `earlier` was captured after `self`,
+    /// returns [`None`].
+    #[must_use]
+    pub fn checked_sub(self, earlier: Self) -> Option<Duration> {
+        if earlier.anchor.saturating_duration_since(self.anchor) > Duration::ZERO {
+            return None;
+        }
+        self.elapsed.checked_sub(earlier.elapsed)
+    }
+
+    /// Returns the time accumulated between `earlier` and `self`. If less
+    /// time had elapsed at `self`, or if `earlier` was captured after `self`,
+    /// returns [`Duration::ZERO`].
+    #[must_use]
+    pub fn saturating_sub(self, earlier: Self) -> Duration {
+        self.checked_sub(earlier).unwrap_or(Duration::ZERO)
+    }
+}
+
+impl<I: Instant> Stopwatch<I> {
+    /// Captures the state of the stopwatch at now.
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot<I> {
+        self.snapshot_at(I::now())
+    }
+
+    /// Captures the state of the stopwatch as if the current time were
+    /// `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`elapsed_at`](Self::elapsed_at) for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    #[must_use]
+    pub fn snapshot_at(&self, anchor: I) -> Snapshot<I> {
+        Snapshot {
+            elapsed: self.elapsed_at(anchor),
+            running: self.is_running(),
+            anchor,
+        }
+    }
+}
+
+impl<I: Instant> ops::Sub for Snapshot<I> {
+    type Output = Duration;
+
+    /// Returns the time accumulated between `earlier` and `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if less time had elapsed at `self` than at `earlier`, or if
+    /// `earlier` was captured after `self`. See [`Snapshot::checked_sub`] for
+    /// a non-panicking version.
+    #[track_caller]
+    fn sub(self, earlier: Self) -> Duration {
+        self.checked_sub(earlier)
+            .expect("attempt to subtract snapshots with underflow")
+    }
+}
+
+/// An iterator capturing snapshots of many stopwatches at the same anchor.
+///
+/// This is returned by [`Snapshot::each_at`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Snapshots<T, I: Instant> {
+    inner: T,
+    anchor: I,
+}
+
+impl<'a, T, I> Iterator for Snapshots<T, I>
+where
+    T: Iterator<Item = &'a Stopwatch<I>>,
+    I: Instant + 'a,
+{
+    type Item = Snapshot<I>;
+
+    fn next(&mut self) -> Option<Snapshot<I>> {
+        self.inner.next().map(|sw| sw.snapshot_at(self.anchor))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, I> DoubleEndedIterator for Snapshots<T, I>
+where
+    T: DoubleEndedIterator<Item = &'a Stopwatch<I>>,
+    I: Instant + 'a,
+{
+    fn next_back(&mut self) -> Option<Snapshot<I>> {
+        self.inner.next_back().map(|sw| sw.snapshot_at(self.anchor))
+    }
+}
+
+impl<'a, T, I> ExactSizeIterator for Snapshots<T, I>
+where
+    T: ExactSizeIterator<Item = &'a Stopwatch<I>>,
+    I: Instant + 'a,
+{
+}
+
+impl<'a, T, I> FusedIterator for Snapshots<T, I>
+where
+    T: FusedIterator<Item = &'a Stopwatch<I>>,
+    I: Instant + 'a,
+{
+}
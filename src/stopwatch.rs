@@ -8,8 +8,7 @@ use core::hash::{Hash, Hasher};
 use core::ops;
 use core::time::Duration;
 
-use crate::canonical::Canonical;
-use crate::Instant;
+use crate::{CanonicalStopwatch, Instant};
 
 /// A stopwatch measures and accumulates elapsed time between starts and stops.
 ///
@@ -772,6 +771,195 @@ impl<I: Instant> Stopwatch<I> {
         self.elapsed = new;
         Some(self)
     }
+
+    /// Converts the stopwatch to use the timekeeping type `J`, pairing the
+    /// current times of `I` and `J`.
+    ///
+    /// # Notes
+    ///
+    /// See [`convert_at`](Self::convert_at) for details about how a
+    /// running stopwatch is converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::{Sw, SystemSw};
+    /// # use core::time::Duration;
+    /// let sw = Sw::with_elapsed(Duration::from_secs(1));
+    /// let system_sw: SystemSw = sw.convert();
+    /// assert_eq!(system_sw.elapsed(), Duration::from_secs(1));
+    /// ```
+    #[must_use]
+    pub fn convert<J: Instant>(self) -> Stopwatch<J> {
+        self.convert_at(I::now(), J::now())
+    }
+
+    /// Converts the stopwatch to use the timekeeping type `J`, as if
+    /// `anchor_i` and `anchor_j` were the same point in time.
+    ///
+    /// The elapsed time is carried over. If the stopwatch is running, it
+    /// remains running, and its start is translated by its offset from
+    /// `anchor_i` to the same offset from `anchor_j`.
+    ///
+    /// # Notes
+    ///
+    /// If the translated start is not representable by `J`, the elapsed time
+    /// is instead [synced](Self::elapsed_at) at `anchor_i`, and the stopwatch
+    /// is started at `anchor_j`. If the new elapsed time overflows, it is
+    /// saturated to [`Duration::MAX`]. Use
+    /// [`checked_convert_at`](Self::checked_convert_at) to explicitly check
+    /// for this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::{Sw, SystemSw};
+    /// # use core::time::Duration;
+    /// # use std::time::{Instant, SystemTime};
+    /// let anchor_i = Instant::now();
+    /// let anchor_j = SystemTime::now();
+    ///
+    /// let sw = Sw::new_started_at(anchor_i - Duration::from_secs(1));
+    /// let system_sw: SystemSw = sw.convert_at(anchor_i, anchor_j);
+    /// assert!(system_sw.is_running());
+    /// assert_eq!(system_sw.elapsed_at(anchor_j), Duration::from_secs(1));
+    /// ```
+    #[must_use]
+    pub fn convert_at<J: Instant>(self, anchor_i: I, anchor_j: J) -> Stopwatch<J> {
+        self.checked_convert_at(anchor_i, anchor_j)
+            .unwrap_or_else(|| {
+                let elapsed = self.elapsed_at(anchor_i);
+                Stopwatch::from_raw(elapsed, Some(anchor_j))
+            })
+    }
+
+    /// Converts the stopwatch to use the timekeeping type `J`, pairing the
+    /// current times of `I` and `J`. If the translated start is not
+    /// representable by `J`, returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// See [`convert_at`](Self::convert_at) for details about how a
+    /// running stopwatch is converted.
+    #[must_use]
+    pub fn checked_convert<J: Instant>(self) -> Option<Stopwatch<J>> {
+        self.checked_convert_at(I::now(), J::now())
+    }
+
+    /// Converts the stopwatch to use the timekeeping type `J`, as if
+    /// `anchor_i` and `anchor_j` were the same point in time. If the translated
+    /// start is not representable by `J`, returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// See [`convert_at`](Self::convert_at) for details about how a
+    /// running stopwatch is converted.
+    #[must_use]
+    pub fn checked_convert_at<J: Instant>(self, anchor_i: I, anchor_j: J) -> Option<Stopwatch<J>> {
+        let start = match self.start {
+            None => None,
+            Some(start) => {
+                // see `saturate_anchor_to_start` for why this orders them
+                let past = anchor_i.saturating_duration_since(start);
+                let future = start.saturating_duration_since(anchor_i);
+                if future < past {
+                    Some(anchor_j.checked_sub(past)?)
+                } else {
+                    Some(anchor_j.checked_add(future)?)
+                }
+            }
+        };
+        Some(Stopwatch::from_raw(self.elapsed, start))
+    }
+
+    /// Returns the canonical shape of the stopwatch, which defines its
+    /// equality and hashing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::{CanonicalStopwatch, Sw};
+    /// # use core::time::Duration;
+    /// let sw = Sw::with_elapsed(Duration::from_secs(1));
+    /// assert_eq!(sw.canonical(), CanonicalStopwatch::Stopped(Duration::from_secs(1)));
+    /// ```
+    #[must_use]
+    pub fn canonical(&self) -> CanonicalStopwatch<I> {
+        CanonicalStopwatch::from(*self)
+    }
+
+    /// Returns an equal stopwatch in canonical shape. If running, as much of
+    /// the elapsed time as possible is moved into the start, leaving zero
+    /// elapsed time unless the stopwatch is
+    /// [unbounded](CanonicalStopwatch::Unbounded).
+    ///
+    /// Stopwatches in canonical shape are equal if and only if their fields
+    /// are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::Sw;
+    /// # use core::time::Duration;
+    /// # use std::time::Instant;
+    /// let now = Instant::now();
+    /// let sw = Sw::from_raw(Duration::from_secs(1), Some(now)).canonicalize();
+    /// assert_eq!(sw.elapsed, Duration::ZERO);
+    /// assert_eq!(sw.start, Some(now - Duration::from_secs(1)));
+    /// ```
+    #[must_use]
+    pub fn canonicalize(self) -> Self {
+        match self.canonical() {
+            CanonicalStopwatch::Stopped(elapsed) => Self::with_elapsed(elapsed),
+            CanonicalStopwatch::Bounded(start) => Self::new_started_at(start),
+            CanonicalStopwatch::Unbounded { start, excess } => Self::from_raw(excess, Some(start)),
+        }
+    }
+
+    /// Returns a stopwatch whose start, if running, is now, with the time
+    /// elapsed until now moved into the elapsed time.
+    ///
+    /// # Notes
+    ///
+    /// See [`normalize_at`](Self::normalize_at) for notes about what happens
+    /// if overflow occurs.
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        self.normalize_at(I::now())
+    }
+
+    /// Returns a stopwatch whose start, if running, is `anchor`, with the
+    /// time elapsed until `anchor` moved into the elapsed time.
+    ///
+    /// Stopwatches normalized at the same anchor, which is no earlier than
+    /// either of their starts and doesn't overflow their elapsed time, can be
+    /// compared by their fields. Otherwise, compare their
+    /// [`CanonicalStopwatch`]s, returned by [`canonical`](Self::canonical).
+    ///
+    /// # Notes
+    ///
+    /// If `anchor` is before the start, the start is left unchanged. If the
+    /// elapsed time overflows, it is saturated to [`Duration::MAX`], and the
+    /// result is no longer equal to `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::Sw;
+    /// # use core::time::Duration;
+    /// # use std::time::Instant;
+    /// let start = Instant::now();
+    /// let anchor = start + Duration::from_secs(1);
+    /// let sw = Sw::from_raw(Duration::MAX, Some(start));
+    /// assert_eq!(sw.normalize_at(start).start, Some(start));
+    /// assert_eq!(sw.normalize_at(anchor).start, Some(anchor));
+    /// ```
+    #[must_use]
+    pub fn normalize_at(mut self, mut anchor: I) -> Self {
+        self.saturate_anchor_to_start(&mut anchor);
+        self.saturating_sync_elapsed_at(anchor);
+        self
+    }
 }
 
 // private methods
@@ -883,9 +1071,17 @@ impl<I: Instant> PartialEq for Stopwatch<I> {
     /// Tests for equality between `self` and `rhs`.
     ///
     /// Stopwatches are equal if whether they are running and their elapsed time
-    /// are equal.
+    /// are equal. This is decided by comparing their
+    /// [canonical shapes](Self::canonical).
+    ///
+    /// # Notes
+    ///
+    /// Comparing an [unbounded](CanonicalStopwatch::Unbounded) stopwatch is
+    /// much slower than comparing others. To compare many of them repeatedly,
+    /// compute their [canonical shapes](Self::canonical) once and compare
+    /// those instead.
     fn eq(&self, rhs: &Self) -> bool {
-        Canonical::new(*self) == Canonical::new(*rhs)
+        self.canonical() == rhs.canonical()
     }
 }
 
@@ -900,6 +1096,6 @@ impl<I: Instant + Hash> Hash for Stopwatch<I> {
     /// `I` (the [`Instant`] type used by the stopwatch) must implement
     /// [`Hash`].
     fn hash<H: Hasher>(&self, state: &mut H) {
-        Canonical::new(*self).hash(state);
+        self.canonical().hash(state);
     }
 }
@@ -0,0 +1,154 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::iter::Sum;
+use core::time::Duration;
+
+use crate::{Instant, Stopwatch};
+
+impl<I: Instant> Stopwatch<I> {
+    /// Sums the stopwatches of `iter` at now. See
+    /// [`checked_sum_at`](Self::checked_sum_at) for details.
+    #[must_use]
+    pub fn checked_sum<T: IntoIterator<Item = Self>>(iter: T) -> Option<Self> {
+        Self::checked_sum_at(iter, I::now())
+    }
+
+    /// Sums the stopwatches of `iter`, measured as if the current time were
+    /// `anchor`. If overflow occurs, returns [`None`].
+    ///
+    /// The sum is a stopped stopwatch whose elapsed time is the total time
+    /// elapsed of every stopwatch at `anchor`. It is stopped even if some of
+    /// them are running, since several running stopwatches accumulate time
+    /// faster than any single stopwatch can. To follow a running total, sum
+    /// again at a later anchor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libsw_core::Sw;
+    /// # use core::time::Duration;
+    /// # use std::time::Instant;
+    /// let start = Instant::now();
+    /// let anchor = start + Duration::from_secs(5);
+    /// let workers = [Sw::new_started_at(start), Sw::with_elapsed(Duration::from_secs(2))];
+    ///
+    /// let sum = Sw::checked_sum_at(workers, anchor).unwrap();
+    /// assert_eq!(sum.elapsed, Duration::from_secs(7));
+    /// assert!(sum.is_stopped());
+    /// ```
+    #[must_use]
+    pub fn checked_sum_at<T: IntoIterator<Item = Self>>(iter: T, anchor: I) -> Option<Self> {
+        let mut elapsed = Duration::ZERO;
+        for sw in iter {
+            elapsed = elapsed.checked_add(sw.checked_elapsed_at(anchor)?)?;
+        }
+        Some(Self::with_elapsed(elapsed))
+    }
+
+    /// Sums the stopwatches of `iter` at now. See
+    /// [`saturating_sum_at`](Self::saturating_sum_at) for details.
+    #[must_use]
+    pub fn saturating_sum<T: IntoIterator<Item = Self>>(iter: T) -> Self {
+        Self::saturating_sum_at(iter, I::now())
+    }
+
+    /// Sums the stopwatches of `iter`, measured as if the current time were
+    /// `anchor`. If overflow occurs, the elapsed time is saturated to
+    /// [`Duration::MAX`].
+    ///
+    /// As with [`checked_sum_at`](Self::checked_sum_at), the sum is stopped.
+    #[must_use]
+    pub fn saturating_sum_at<T: IntoIterator<Item = Self>>(iter: T, anchor: I) -> Self {
+        let mut elapsed = Duration::ZERO;
+        for sw in iter {
+            elapsed = elapsed.saturating_add(sw.elapsed_at(anchor));
+        }
+        Self::with_elapsed(elapsed)
+    }
+}
+
+impl<I: Instant> Sum for Stopwatch<I> {
+    /// Sums the stopwatches of `iter` at now.
+    ///
+    /// Currently this is an alias to [`Stopwatch::checked_sum`], but that
+    /// is not a stable guarentee. If you need a guarentee on the
+    /// implementation, use the [checked](Stopwatch::checked_sum) or
+    /// [saturating](Stopwatch::saturating_sum) methods explicitly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if overflow occurs.
+    #[track_caller]
+    fn sum<T: Iterator<Item = Self>>(iter: T) -> Self {
+        Self::checked_sum(iter).expect("attempt to sum stopwatches with overflow")
+    }
+}
+
+impl<'a, I: Instant> Sum<&'a Stopwatch<I>> for Stopwatch<I> {
+    /// Sums the stopwatches of `iter` at now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if overflow occurs.
+    #[track_caller]
+    fn sum<T: Iterator<Item = &'a Self>>(iter: T) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<I: Instant> Sum<Stopwatch<I>> for Duration {
+    /// Sums the time elapsed of the stopwatches of `iter`, each measured at
+    /// the same instant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if overflow occurs.
+    #[track_caller]
+    fn sum<T: Iterator<Item = Stopwatch<I>>>(iter: T) -> Self {
+        iter.sum::<Stopwatch<I>>().elapsed
+    }
+}
+
+impl<'a, I: Instant> Sum<&'a Stopwatch<I>> for Duration {
+    /// Sums the time elapsed of the stopwatches of `iter`, each measured at
+    /// the same instant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if overflow occurs.
+    #[track_caller]
+    fn sum<T: Iterator<Item = &'a Stopwatch<I>>>(iter: T) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<I: Instant> FromIterator<Duration> for Stopwatch<I> {
+    /// Returns a stopped stopwatch whose elapsed time is the sum of `iter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if overflow occurs.
+    #[track_caller]
+    fn from_iter<T: IntoIterator<Item = Duration>>(iter: T) -> Self {
+        let mut sw = Self::new();
+        sw.extend(iter);
+        sw
+    }
+}
+
+impl<I: Instant> Extend<Duration> for Stopwatch<I> {
+    /// Adds each duration of `iter` to the elapsed time. The time elapsed
+    /// since the last start is not affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if overflow occurs.
+    #[track_caller]
+    fn extend<T: IntoIterator<Item = Duration>>(&mut self, iter: T) {
+        for dur in iter {
+            *self += dur;
+        }
+    }
+}
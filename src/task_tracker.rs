@@ -0,0 +1,292 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate std;
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::slice;
+use core::time::Duration;
+use std::vec::Vec;
+
+use crate::{Instant, Stopwatch};
+
+/// A time tracker which switches between categories, keeping exactly one
+/// category running at a time.
+///
+/// Each category `K` is measured with its own [`Stopwatch`]. Time not spent
+/// in any category is measured as idle time. Every switch stops the current
+/// stopwatch and starts the next at the same anchor, so the totals of all
+/// categories and idle time always sum to the time since the tracker
+/// started.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::TaskTracker;
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let start = Instant::now();
+/// let at = |mins: u64| start + Duration::from_secs(mins * 60);
+/// let mut tracker = TaskTracker::new_started_at(start);
+///
+/// tracker.switch_to_at("review", at(5));
+/// tracker.switch_to_at("meeting", at(35));
+/// tracker.switch_to_at("review", at(65));
+/// tracker.idle_at(at(80));
+///
+/// assert_eq!(tracker.total_at("review", at(90)), Duration::from_secs(45 * 60));
+/// assert_eq!(tracker.idle_total_at(at(90)), Duration::from_secs(15 * 60));
+/// assert_eq!(tracker.wall_total_at(at(90)), Duration::from_secs(90 * 60));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TaskTracker<K, I: Instant> {
+    tasks: Vec<(K, Stopwatch<I>)>,
+    idle: Stopwatch<I>,
+    /// The index of the running task, or [`None`] if idle.
+    current: Option<usize>,
+}
+
+impl<K: Eq, I: Instant> TaskTracker<K, I> {
+    /// Returns a tracker with no categories, idle since now.
+    #[must_use]
+    pub fn new_started() -> Self {
+        Self::new_started_at(I::now())
+    }
+
+    /// Returns a tracker with no categories, idle since the given instant.
+    #[must_use]
+    pub const fn new_started_at(start: I) -> Self {
+        Self {
+            tasks: Vec::new(),
+            idle: Stopwatch::new_started_at(start),
+            current: None,
+        }
+    }
+
+    /// Returns the running category, or [`None`] if idle.
+    #[must_use]
+    pub fn current(&self) -> Option<&K> {
+        self.current.map(|index| &self.tasks[index].0)
+    }
+
+    /// Returns `true` if no category is running.
+    #[must_use]
+    pub const fn is_idle(&self) -> bool {
+        self.current.is_none()
+    }
+
+    /// Returns the number of categories tracked.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if no categories have been tracked.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Stops the running category, or idle time, and starts `key`.
+    pub fn switch_to(&mut self, key: K) {
+        self.switch_to_at(key, I::now());
+    }
+
+    /// Stops the running category, or idle time, and starts `key`, as if the
+    /// current time were `anchor`. If `key` is already running, nothing
+    /// happens.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    pub fn switch_to_at(&mut self, key: K, anchor: I) {
+        let index = if let Some(index) = self.tasks.iter().position(|(k, _)| *k == key) {
+            index
+        } else {
+            self.tasks.push((key, Stopwatch::new()));
+            self.tasks.len() - 1
+        };
+        if self.current != Some(index) {
+            self.running_mut().stop_at(anchor);
+            self.tasks[index].1.start_at(anchor);
+            self.current = Some(index);
+        }
+    }
+
+    /// Stops the running category and starts idle time.
+    pub fn idle(&mut self) {
+        self.idle_at(I::now());
+    }
+
+    /// Stops the running category and starts idle time, as if the current
+    /// time were `anchor`. If already idle, nothing happens.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    pub fn idle_at(&mut self, anchor: I) {
+        if self.current.is_some() {
+            self.running_mut().stop_at(anchor);
+            self.idle.start_at(anchor);
+            self.current = None;
+        }
+    }
+
+    /// Returns the stopwatch of `key`, if it has been tracked.
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&Stopwatch<I>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.tasks
+            .iter()
+            .find_map(|(k, sw)| if k.borrow() == key { Some(sw) } else { None })
+    }
+
+    /// Returns the total time spent in `key`.
+    #[must_use]
+    pub fn total<Q>(&self, key: &Q) -> Duration
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.total_at(key, I::now())
+    }
+
+    /// Returns the total time spent in `key`, measured as if the current time
+    /// were `anchor`. If `key` has not been tracked, returns
+    /// [`Duration::ZERO`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    #[must_use]
+    pub fn total_at<Q>(&self, key: &Q, anchor: I) -> Duration
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.get(key)
+            .map_or(Duration::ZERO, |sw| sw.elapsed_at(anchor))
+    }
+
+    /// Returns the total idle time.
+    #[must_use]
+    pub fn idle_total(&self) -> Duration {
+        self.idle_total_at(I::now())
+    }
+
+    /// Returns the total idle time, measured as if the current time were
+    /// `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`, as well as what happens if overflow occurs.
+    #[must_use]
+    pub fn idle_total_at(&self, anchor: I) -> Duration {
+        self.idle.elapsed_at(anchor)
+    }
+
+    /// Returns the total time across every category and idle time.
+    #[must_use]
+    pub fn wall_total(&self) -> Duration {
+        self.wall_total_at(I::now())
+    }
+
+    /// Returns the total time across every category and idle time, measured
+    /// as if the current time were `anchor`. If overflow occurs, the total is
+    /// saturated to [`Duration::MAX`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::elapsed_at`] for notes about the chronology of
+    /// `anchor`.
+    #[must_use]
+    pub fn wall_total_at(&self, anchor: I) -> Duration {
+        self.tasks
+            .iter()
+            .map(|(_, sw)| sw.elapsed_at(anchor))
+            .fold(self.idle_total_at(anchor), Duration::saturating_add)
+    }
+
+    /// Returns an iterator over the categories and their stopwatches, in the
+    /// order they were first tracked.
+    #[must_use]
+    pub fn iter(&self) -> Tasks<'_, K, I> {
+        Tasks {
+            inner: self.tasks.iter(),
+        }
+    }
+}
+
+// private methods
+impl<K, I: Instant> TaskTracker<K, I> {
+    /// Returns the running stopwatch.
+    fn running_mut(&mut self) -> &mut Stopwatch<I> {
+        match self.current {
+            Some(index) => &mut self.tasks[index].1,
+            None => &mut self.idle,
+        }
+    }
+}
+
+impl<'a, K: Eq, I: Instant> IntoIterator for &'a TaskTracker<K, I> {
+    type Item = (&'a K, &'a Stopwatch<I>);
+    type IntoIter = Tasks<'a, K, I>;
+
+    fn into_iter(self) -> Tasks<'a, K, I> {
+        self.iter()
+    }
+}
+
+/// An iterator over the categories and stopwatches in a [`TaskTracker`], in
+/// the order they were first tracked.
+///
+/// This is returned by [`TaskTracker::iter`].
+pub struct Tasks<'a, K, I: Instant> {
+    inner: slice::Iter<'a, (K, Stopwatch<I>)>,
+}
+
+impl<'a, K, I: Instant> Iterator for Tasks<'a, K, I> {
+    type Item = (&'a K, &'a Stopwatch<I>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, sw)| (key, sw))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, I: Instant> DoubleEndedIterator for Tasks<'_, K, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, sw)| (key, sw))
+    }
+}
+
+impl<K, I: Instant> ExactSizeIterator for Tasks<'_, K, I> {}
+
+impl<K, I: Instant> FusedIterator for Tasks<'_, K, I> {}
+
+impl<K, I: Instant> Clone for Tasks<'_, K, I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K: fmt::Debug, I: Instant> fmt::Debug for Tasks<'_, K, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tasks").field("inner", &self.inner).finish()
+    }
+}
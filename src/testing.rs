@@ -0,0 +1,332 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate proptest;
+
+use core::time::Duration;
+use proptest::prelude::*;
+
+use crate::{duration_from_nanos, Instant, Stopwatch};
+
+/// Generators of [`Stopwatch`]es for property testing with [`proptest`](mod@proptest).
+///
+/// Running stopwatches are started near a base instant, so anchors generated
+/// by [`anchor`](Self::anchor) exercise starts both before and after them.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{Sw, StopwatchStrategies};
+/// # use proptest::prelude::*;
+/// # use std::time::Instant;
+/// let strategies = StopwatchStrategies::new(Instant::now());
+///
+/// proptest!(|(sw in strategies.any(), anchor in strategies.anchor())| {
+///     prop_assert!(sw.checked_elapsed_at(anchor).is_some() || sw.is_running());
+/// });
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct StopwatchStrategies<I: Instant> {
+    base: I,
+}
+
+/// The largest distance of generated instants from the base instant.
+const SPREAD: Duration = Duration::from_secs(1000);
+
+impl<I: Instant + 'static> StopwatchStrategies<I> {
+    /// Returns generators around the instant `base`.
+    ///
+    /// `base` should be at least 1000 seconds away from the limits of `I`,
+    /// which is the case for the current time of most clocks.
+    #[must_use]
+    pub const fn new(base: I) -> Self {
+        Self { base }
+    }
+
+    /// Returns a strategy for instants within 1000 seconds of the base
+    /// instant.
+    pub fn anchor(&self) -> BoxedStrategy<I> {
+        let base = self.base;
+        (any::<bool>(), duration_upto(SPREAD))
+            .prop_filter_map("instant is not representable", move |(after, offset)| {
+                if after {
+                    base.checked_add(offset)
+                } else {
+                    base.checked_sub(offset)
+                }
+            })
+            .boxed()
+    }
+
+    /// Returns a strategy for durations, biased towards zero and
+    /// [`Duration::MAX`].
+    pub fn duration(&self) -> BoxedStrategy<Duration> {
+        prop_oneof![
+            Just(Duration::ZERO),
+            Just(Duration::MAX),
+            duration_upto(SPREAD),
+            near_max(),
+            any::<(u64, u32)>()
+                .prop_map(|(secs, nanos)| Duration::new(secs, nanos % 1_000_000_000)),
+        ]
+        .boxed()
+    }
+
+    /// Returns a strategy for stopped stopwatches.
+    pub fn stopped(&self) -> BoxedStrategy<Stopwatch<I>> {
+        self.duration().prop_map(Stopwatch::with_elapsed).boxed()
+    }
+
+    /// Returns a strategy for running stopwatches, which are neither near
+    /// overflow nor unbounded.
+    pub fn running(&self) -> BoxedStrategy<Stopwatch<I>> {
+        (duration_upto(SPREAD), self.anchor())
+            .prop_map(|(elapsed, start)| Stopwatch::from_raw(elapsed, Some(start)))
+            .boxed()
+    }
+
+    /// Returns a strategy for stopwatches, stopped or running, whose elapsed
+    /// time is within 1000 seconds of [`Duration::MAX`].
+    pub fn near_overflow(&self) -> BoxedStrategy<Stopwatch<I>> {
+        (near_max(), proptest::option::of(self.anchor()))
+            .prop_map(|(elapsed, start)| Stopwatch::from_raw(elapsed, start))
+            .boxed()
+    }
+
+    /// Returns a strategy for running stopwatches whose start would be before
+    /// the earliest representable instant if their elapsed time were
+    /// subtracted from it.
+    pub fn unbounded(&self) -> BoxedStrategy<Stopwatch<I>> {
+        (self.duration(), self.anchor())
+            .prop_filter_map("stopwatch is bounded", |(elapsed, start)| {
+                if start.checked_sub(elapsed).is_none() {
+                    Some(Stopwatch::from_raw(elapsed, Some(start)))
+                } else {
+                    None
+                }
+            })
+            .boxed()
+    }
+
+    /// Returns a strategy for stopwatches in any of the above states.
+    pub fn any(&self) -> BoxedStrategy<Stopwatch<I>> {
+        prop_oneof![
+            self.stopped(),
+            self.running(),
+            self.near_overflow(),
+            self.unbounded(),
+        ]
+        .boxed()
+    }
+}
+
+/// Returns a strategy for durations up to `max`.
+fn duration_upto(max: Duration) -> impl Strategy<Value = Duration> {
+    (0..=max.as_nanos()).prop_map(|nanos| duration_from_nanos(nanos).unwrap_or(Duration::MAX))
+}
+
+/// Returns a strategy for durations within `SPREAD` of [`Duration::MAX`].
+fn near_max() -> impl Strategy<Value = Duration> {
+    duration_upto(SPREAD).prop_map(|offset| Duration::MAX.saturating_sub(offset))
+}
+
+/// A straightforward model of a [`Stopwatch`], for differential testing.
+///
+/// Totals are computed in nanoseconds with `u128`, which cannot overflow for
+/// the sum of two durations, and are then checked or saturated explicitly.
+/// Each method documents the [`Stopwatch`] method it models.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{ReferenceStopwatch, Sw};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let now = Instant::now();
+/// let mut sw = Sw::with_elapsed_started(Duration::MAX);
+/// let mut model = ReferenceStopwatch::from(sw);
+///
+/// let later = now + Duration::from_secs(1);
+/// assert_eq!(sw.checked_stop_at(later), model.checked_stop_at(later));
+/// assert!(model.matches(&sw));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReferenceStopwatch<I: Instant> {
+    /// The time elapsed before the last start.
+    pub elapsed: Duration,
+    /// The instant of the last start, if running.
+    pub start: Option<I>,
+}
+
+impl<I: Instant> ReferenceStopwatch<I> {
+    /// Returns `true` if the fields of `sw` are identical to the model.
+    #[must_use]
+    pub fn matches(&self, sw: &Stopwatch<I>) -> bool
+    where
+        I: PartialEq,
+    {
+        self.elapsed == sw.elapsed && self.start == sw.start
+    }
+
+    /// Models [`Stopwatch::checked_elapsed_at`].
+    #[must_use]
+    pub fn checked_elapsed_at(&self, anchor: I) -> Option<Duration> {
+        duration_from_nanos(self.total_at(anchor))
+    }
+
+    /// Models [`Stopwatch::elapsed_at`].
+    #[must_use]
+    pub fn elapsed_at(&self, anchor: I) -> Duration {
+        self.checked_elapsed_at(anchor).unwrap_or(Duration::MAX)
+    }
+
+    /// Models [`Stopwatch::start_at`].
+    pub fn start_at(&mut self, anchor: I) {
+        self.start = Some(anchor);
+    }
+
+    /// Models [`Stopwatch::stop_at`].
+    pub fn stop_at(&mut self, anchor: I) {
+        self.elapsed = self.elapsed_at(anchor);
+        self.start = None;
+    }
+
+    /// Models [`Stopwatch::checked_stop_at`].
+    #[must_use]
+    pub fn checked_stop_at(&mut self, anchor: I) -> bool {
+        match self.checked_elapsed_at(anchor) {
+            Some(elapsed) => {
+                self.elapsed = elapsed;
+                self.start = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Models [`Stopwatch::toggle_at`].
+    pub fn toggle_at(&mut self, anchor: I) {
+        if self.start.is_some() {
+            self.stop_at(anchor);
+        } else {
+            self.start_at(anchor);
+        }
+    }
+
+    /// Models [`Stopwatch::checked_toggle_at`].
+    #[must_use]
+    pub fn checked_toggle_at(&mut self, anchor: I) -> bool {
+        if self.start.is_some() {
+            self.checked_stop_at(anchor)
+        } else {
+            self.start_at(anchor);
+            true
+        }
+    }
+
+    /// Models [`Stopwatch::saturating_add`].
+    #[must_use]
+    pub fn saturating_add(mut self, dur: Duration) -> Self {
+        let sum = self.elapsed.as_nanos() + dur.as_nanos();
+        self.elapsed = duration_from_nanos(sum).unwrap_or(Duration::MAX);
+        self
+    }
+
+    /// Models [`Stopwatch::checked_add`].
+    #[must_use]
+    pub fn checked_add(mut self, dur: Duration) -> Option<Self> {
+        self.elapsed = duration_from_nanos(self.elapsed.as_nanos() + dur.as_nanos())?;
+        Some(self)
+    }
+
+    /// Models [`Stopwatch::saturating_sub_at`].
+    #[must_use]
+    pub fn saturating_sub_at(mut self, dur: Duration, anchor: I) -> Self {
+        let total = self.total_at(anchor).min(Duration::MAX.as_nanos());
+        self.elapsed =
+            duration_from_nanos(total.saturating_sub(dur.as_nanos())).unwrap_or(Duration::ZERO);
+        self.start = self.start.map(|start| latest(start, anchor));
+        self
+    }
+
+    /// Models [`Stopwatch::checked_sub_at`].
+    #[must_use]
+    pub fn checked_sub_at(mut self, dur: Duration, anchor: I) -> Option<Self> {
+        let total = duration_from_nanos(self.total_at(anchor))?;
+        self.elapsed = total.checked_sub(dur)?;
+        self.start = self.start.map(|start| latest(start, anchor));
+        Some(self)
+    }
+
+    /// Models [`Stopwatch::convert_at`].
+    #[must_use]
+    pub fn convert_at<J: Instant>(self, anchor_i: I, anchor_j: J) -> ReferenceStopwatch<J> {
+        self.checked_convert_at(anchor_i, anchor_j)
+            .unwrap_or_else(|| ReferenceStopwatch {
+                elapsed: self.elapsed_at(anchor_i),
+                start: Some(anchor_j),
+            })
+    }
+
+    /// Models [`Stopwatch::checked_convert_at`].
+    #[must_use]
+    pub fn checked_convert_at<J: Instant>(
+        self,
+        anchor_i: I,
+        anchor_j: J,
+    ) -> Option<ReferenceStopwatch<J>> {
+        let start = match self.start {
+            None => None,
+            Some(start) => {
+                // signed offset of the start from the anchor, in nanoseconds
+                let ahead = start.saturating_duration_since(anchor_i).as_nanos();
+                let behind = anchor_i.saturating_duration_since(start).as_nanos();
+                if ahead >= behind {
+                    Some(anchor_j.checked_add(duration_from_nanos(ahead - behind)?)?)
+                } else {
+                    Some(anchor_j.checked_sub(duration_from_nanos(behind - ahead)?)?)
+                }
+            }
+        };
+        Some(ReferenceStopwatch {
+            elapsed: self.elapsed,
+            start,
+        })
+    }
+}
+
+// private methods
+impl<I: Instant> ReferenceStopwatch<I> {
+    /// Returns the exact total time elapsed at `anchor`, in nanoseconds.
+    fn total_at(&self, anchor: I) -> u128 {
+        let running = self.start.map_or(Duration::ZERO, |start| {
+            anchor.saturating_duration_since(start)
+        });
+        self.elapsed.as_nanos() + running.as_nanos()
+    }
+}
+
+impl<I: Instant> From<Stopwatch<I>> for ReferenceStopwatch<I> {
+    fn from(sw: Stopwatch<I>) -> Self {
+        Self {
+            elapsed: sw.elapsed,
+            start: sw.start,
+        }
+    }
+}
+
+impl<I: Instant> From<ReferenceStopwatch<I>> for Stopwatch<I> {
+    fn from(model: ReferenceStopwatch<I>) -> Self {
+        Self::from_raw(model.elapsed, model.start)
+    }
+}
+
+/// Returns the later of two instants.
+fn latest<I: Instant>(a: I, b: I) -> I {
+    if b.saturating_duration_since(a).is_zero() {
+        a
+    } else {
+        b
+    }
+}
@@ -0,0 +1,98 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use super::manual::ManualInstant;
+
+type CanonicalStopwatch = crate::CanonicalStopwatch<ManualInstant>;
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+#[test]
+fn categories() {
+    let stopped = Stopwatch::with_elapsed(Duration::from_secs(3));
+    assert_eq!(
+        stopped.canonical(),
+        CanonicalStopwatch::Stopped(Duration::from_secs(3))
+    );
+    assert!(stopped.canonical().is_stopped());
+
+    let bounded = Stopwatch::from_raw(Duration::from_secs(3), Some(ManualInstant::from_secs(10)));
+    assert_eq!(
+        bounded.canonical(),
+        CanonicalStopwatch::Bounded(ManualInstant::from_secs(7))
+    );
+    assert!(bounded.canonical().is_running());
+
+    let unbounded =
+        Stopwatch::from_raw(Duration::from_secs(30), Some(ManualInstant::from_secs(10)));
+    assert_eq!(
+        unbounded.canonical(),
+        CanonicalStopwatch::Unbounded {
+            start: ManualInstant::from_secs(0),
+            excess: Duration::from_secs(20),
+        },
+    );
+}
+
+#[test]
+fn canonicalize() {
+    let sw = Stopwatch::from_raw(Duration::from_secs(3), Some(ManualInstant::from_secs(10)));
+    let canonical = sw.canonicalize();
+    assert_eq!(canonical.elapsed, Duration::ZERO);
+    assert_eq!(canonical.start, Some(ManualInstant::from_secs(7)));
+    assert_eq!(canonical, sw);
+
+    let stopped = Stopwatch::with_elapsed(Duration::from_secs(3));
+    assert_eq!(stopped.canonicalize().elapsed, Duration::from_secs(3));
+
+    let unbounded =
+        Stopwatch::from_raw(Duration::from_secs(30), Some(ManualInstant::from_secs(10)));
+    let canonical = unbounded.canonicalize();
+    assert_eq!(canonical.elapsed, Duration::from_secs(20));
+    assert_eq!(canonical.start, Some(ManualInstant::from_secs(0)));
+    assert_eq!(canonical, unbounded);
+}
+
+#[test]
+fn unbounded_eq() {
+    let nanos = |nanos| ManualInstant(Duration::from_nanos(nanos));
+    let sw_1 = Stopwatch::from_raw(Duration::from_secs(30), Some(nanos(1_500)));
+    let sw_2 = Stopwatch::from_raw(Duration::new(29, 999_999_500), Some(nanos(1_000)));
+    let sw_3 = Stopwatch::from_raw(Duration::from_secs(30), Some(nanos(1_000)));
+    assert_eq!(sw_1, sw_2);
+    assert_ne!(sw_1, sw_3);
+    assert_ne!(sw_2, sw_3);
+
+    let ahead = Stopwatch::from_raw(Duration::MAX, Some(ManualInstant::from_secs(5)));
+    assert_eq!(ahead.canonical(), ahead.canonicalize().canonical());
+}
+
+#[test]
+fn normalize() {
+    let sw = Stopwatch::from_raw(Duration::from_secs(30), Some(ManualInstant::from_secs(10)));
+    let normalized = sw.normalize_at(ManualInstant::from_secs(15));
+    assert_eq!(normalized.elapsed, Duration::from_secs(35));
+    assert_eq!(normalized.start, Some(ManualInstant::from_secs(15)));
+    assert_eq!(
+        normalized.elapsed_at(ManualInstant::from_secs(20)),
+        sw.elapsed_at(ManualInstant::from_secs(20))
+    );
+
+    let early = sw.normalize_at(ManualInstant::from_secs(5));
+    assert_eq!(early.elapsed, Duration::from_secs(30));
+    assert_eq!(early.start, Some(ManualInstant::from_secs(10)));
+
+    let stopped =
+        Stopwatch::with_elapsed(Duration::from_secs(3)).normalize_at(ManualInstant::from_secs(15));
+    assert_eq!(stopped.start, None);
+}
+
+#[test]
+fn normalize_saturates() {
+    let sw = Stopwatch::from_raw(Duration::MAX, Some(ManualInstant::from_secs(10)));
+    let normalized = sw.normalize_at(ManualInstant::from_secs(15));
+    assert_eq!(normalized.elapsed, Duration::MAX);
+    assert_eq!(normalized.start, Some(ManualInstant::from_secs(15)));
+}
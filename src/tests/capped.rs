@@ -0,0 +1,112 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use super::manual::ManualInstant;
+
+type CappedStopwatch = crate::CappedStopwatch<ManualInstant>;
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+#[test]
+fn clamps_elapsed() {
+    let sw = CappedStopwatch::new_started_at(Duration::from_secs(10), ManualInstant::from_secs(0));
+    assert_eq!(
+        sw.elapsed_at(ManualInstant::from_secs(4)),
+        Duration::from_secs(4)
+    );
+    assert_eq!(
+        sw.remaining_at(ManualInstant::from_secs(4)),
+        Duration::from_secs(6)
+    );
+    assert!(!sw.is_capped_at(ManualInstant::from_secs(4)));
+    assert_eq!(
+        sw.elapsed_at(ManualInstant::from_secs(100)),
+        Duration::from_secs(10)
+    );
+    assert!(sw.is_capped_at(ManualInstant::from_secs(10)));
+
+    let sw = CappedStopwatch::from_stopwatch(
+        Stopwatch::with_elapsed(Duration::from_secs(20)),
+        Duration::from_secs(10),
+    );
+    assert_eq!(sw.stopwatch().elapsed, Duration::from_secs(10));
+}
+
+#[test]
+fn cap_instant() {
+    let mut sw = CappedStopwatch::new(Duration::from_secs(10));
+    assert_eq!(sw.cap_instant(), None);
+    sw.start_at(ManualInstant::from_secs(5));
+    assert_eq!(sw.cap_instant(), Some(ManualInstant::from_secs(15)));
+    sw.stop_at(ManualInstant::from_secs(8));
+    sw.start_at(ManualInstant::from_secs(20));
+    assert_eq!(sw.cap_instant(), Some(ManualInstant::from_secs(27)));
+
+    let sw = CappedStopwatch::new_started_at(Duration::MAX, ManualInstant(Duration::MAX));
+    assert_eq!(sw.cap_instant(), None);
+}
+
+#[test]
+fn enforce_cap() {
+    let mut sw =
+        CappedStopwatch::new_started_at(Duration::from_secs(10), ManualInstant::from_secs(0));
+    assert!(!sw.enforce_cap_at(ManualInstant::from_secs(9)));
+    assert!(sw.is_running());
+    assert!(sw.enforce_cap_at(ManualInstant::from_secs(12)));
+    assert_eq!(
+        sw.stopwatch(),
+        &Stopwatch::with_elapsed(Duration::from_secs(10))
+    );
+
+    // a capped stopwatch can't be restarted
+    sw.start_at(ManualInstant::from_secs(13));
+    assert!(sw.is_stopped());
+
+    let mut sw =
+        CappedStopwatch::new_started_at(Duration::from_secs(10), ManualInstant::from_secs(0));
+    sw.stop_at(ManualInstant::from_secs(15));
+    assert_eq!(
+        sw.stopwatch(),
+        &Stopwatch::with_elapsed(Duration::from_secs(10))
+    );
+}
+
+#[test]
+fn checked_arithmetic() {
+    let sw = CappedStopwatch::new_started_at(Duration::from_secs(10), ManualInstant::from_secs(0));
+    assert_eq!(
+        sw.checked_add_at(Duration::from_secs(3), ManualInstant::from_secs(5))
+            .unwrap()
+            .elapsed_at(ManualInstant::from_secs(5)),
+        Duration::from_secs(8),
+    );
+    assert_eq!(
+        sw.checked_add_at(Duration::from_secs(6), ManualInstant::from_secs(5)),
+        None
+    );
+    assert_eq!(
+        sw.saturating_add(Duration::from_secs(60))
+            .elapsed_at(ManualInstant::from_secs(0)),
+        Duration::from_secs(10)
+    );
+
+    let sub = sw
+        .checked_sub_at(Duration::from_secs(4), ManualInstant::from_secs(30))
+        .unwrap();
+    assert!(sub.is_stopped());
+    assert_eq!(
+        sub.elapsed_at(ManualInstant::from_secs(30)),
+        Duration::from_secs(6)
+    );
+    assert_eq!(
+        sw.checked_sub_at(Duration::from_secs(11), ManualInstant::from_secs(30)),
+        None
+    );
+    assert_eq!(
+        sw.saturating_sub_at(Duration::from_secs(11), ManualInstant::from_secs(30))
+            .elapsed(),
+        Duration::ZERO
+    );
+}
@@ -0,0 +1,75 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use super::{Stopwatch, I};
+use crate::Instant;
+
+type ChromeTrace = crate::ChromeTrace<I>;
+
+fn after(origin: I, nanos: u64) -> I {
+    Instant::checked_add(&origin, Duration::from_nanos(nanos)).unwrap()
+}
+
+#[test]
+fn empty() {
+    let trace = ChromeTrace::new(I::now());
+    assert!(trace.is_empty());
+    assert_eq!(trace.to_string(), "{\"traceEvents\":[\n]}");
+}
+
+#[test]
+fn golden() {
+    let origin = I::now();
+    let mut trace = ChromeTrace::new(origin);
+
+    trace.thread_name(1, "main");
+    trace.thread_name(2, "worker \"2\"");
+    trace.span("load", 2, after(origin, 1_500), after(origin, 2_000_750));
+    trace.lap("lap\n1", 1, after(origin, 42));
+
+    let mut sw = Stopwatch::new_started_at(after(origin, 1_000));
+    trace.record_stop_at("run", 1, &mut sw, after(origin, 3_000));
+    assert!(sw.is_stopped());
+    assert_eq!(sw.elapsed, Duration::from_micros(2));
+
+    // stopped stopwatches record nothing
+    trace.record_stop_at("run", 1, &mut sw, after(origin, 4_000));
+    assert_eq!(trace.len(), 5);
+
+    let mut json = Vec::new();
+    trace.write_json(&mut json).unwrap();
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        r#"{"traceEvents":[
+{"name":"thread_name","ph":"M","pid":1,"tid":1,"args":{"name":"main"}},
+{"name":"thread_name","ph":"M","pid":1,"tid":2,"args":{"name":"worker \"2\""}},
+{"name":"load","ph":"X","ts":1.500,"dur":1999.250,"pid":1,"tid":2},
+{"name":"lap\n1","ph":"i","s":"t","ts":0.042,"pid":1,"tid":1},
+{"name":"run","ph":"X","ts":1.000,"dur":2.000,"pid":1,"tid":1}
+]}"#
+    );
+}
+
+#[test]
+fn saturates_to_origin() {
+    let origin = after(I::now(), 1_000);
+    let before = Instant::checked_sub(&origin, Duration::from_micros(1)).unwrap();
+    let mut trace = ChromeTrace::new(origin);
+
+    trace.lap("early", 0, before);
+    trace.span("backwards", 0, origin, before);
+
+    assert_eq!(
+        trace.to_string(),
+        r#"{"traceEvents":[
+{"name":"early","ph":"i","s":"t","ts":0.000,"pid":1,"tid":0},
+{"name":"backwards","ph":"X","ts":0.000,"dur":0.000,"pid":1,"tid":0}
+]}"#
+    );
+
+    trace.clear();
+    assert!(trace.is_empty());
+}
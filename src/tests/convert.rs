@@ -0,0 +1,64 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use super::manual::ManualInstant;
+use super::DELAY;
+use crate::Instant;
+
+type I = std::time::Instant;
+type Stopwatch = crate::Stopwatch<I>;
+type ManualStopwatch = crate::Stopwatch<ManualInstant>;
+
+#[test]
+fn stopped() {
+    let sw = Stopwatch::with_elapsed(DELAY);
+    let converted: ManualStopwatch = sw.convert();
+    assert_eq!(converted, ManualStopwatch::with_elapsed(DELAY));
+    assert_eq!(sw.checked_convert(), Some(converted));
+}
+
+#[test]
+fn running() {
+    let anchor_i = I::now();
+    let anchor_j = ManualInstant(DELAY * 10);
+
+    // started in the past
+    let sw = Stopwatch::from_raw(DELAY, Some(Instant::checked_sub(&anchor_i, DELAY).unwrap()));
+    let converted = sw.checked_convert_at(anchor_i, anchor_j).unwrap();
+    assert_eq!(converted.start, Some(ManualInstant(DELAY * 9)));
+    assert_eq!(converted.elapsed, DELAY);
+    assert_eq!(converted, sw.convert_at(anchor_i, anchor_j));
+
+    // started in the future
+    let sw = Stopwatch::new_started_at(Instant::checked_add(&anchor_i, DELAY).unwrap());
+    let converted = sw.checked_convert_at(anchor_i, anchor_j).unwrap();
+    assert_eq!(converted.start, Some(ManualInstant(DELAY * 11)));
+
+    // and back again
+    let back: Stopwatch = converted.checked_convert_at(anchor_j, anchor_i).unwrap();
+    assert_eq!(back, sw);
+}
+
+#[test]
+fn unrepresentable() {
+    let anchor_i = I::now();
+    let anchor_j = ManualInstant(DELAY);
+    let sw = Stopwatch::from_raw(
+        DELAY,
+        Some(Instant::checked_sub(&anchor_i, DELAY * 2).unwrap()),
+    );
+
+    assert_eq!(sw.checked_convert_at(anchor_i, anchor_j), None);
+
+    // elapsed time is synced at the anchor instead
+    let converted = sw.convert_at(anchor_i, anchor_j);
+    assert_eq!(converted.start, Some(anchor_j));
+    assert_eq!(converted.elapsed, DELAY * 3);
+
+    // which saturates on overflow
+    let sw = Stopwatch::from_raw(Duration::MAX, sw.start);
+    assert_eq!(sw.convert_at(anchor_i, anchor_j).elapsed, Duration::MAX);
+}
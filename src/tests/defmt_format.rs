@@ -0,0 +1,28 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use defmt::{Format, Formatter};
+
+use super::manual::ManualInstant;
+use crate::Stopwatch;
+
+impl Format for ManualInstant {
+    fn format(&self, fmt: Formatter) {
+        defmt::write!(
+            fmt,
+            "ManualInstant({=u64}.{=u32:09}s)",
+            self.0.as_secs(),
+            self.0.subsec_nanos(),
+        );
+    }
+}
+
+fn assert_format<T: Format + ?Sized>() {}
+
+#[test]
+fn stopwatch_implements_format() {
+    assert_format::<Stopwatch<ManualInstant>>();
+    assert_format::<[Stopwatch<ManualInstant>]>();
+    assert_format::<Option<&Stopwatch<ManualInstant>>>();
+}
@@ -0,0 +1,85 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use super::manual::ManualInstant;
+use crate::{FixedStep, Steps};
+
+fn timestep() -> FixedStep<ManualInstant> {
+    FixedStep::new_started_at(Duration::from_millis(10), 4, ManualInstant::from_millis(0))
+}
+
+#[test]
+fn whole_steps() {
+    let mut timestep = timestep();
+    assert_eq!(
+        timestep.advance_at(ManualInstant::from_millis(5)),
+        Steps {
+            count: 0,
+            alpha: 0.5
+        }
+    );
+    assert_eq!(
+        timestep.advance_at(ManualInstant::from_millis(20)),
+        Steps {
+            count: 2,
+            alpha: 0.0
+        }
+    );
+    assert_eq!(
+        timestep
+            .accumulator()
+            .elapsed_at(ManualInstant::from_millis(20)),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn leftover_carries_over() {
+    let mut timestep = timestep();
+    assert_eq!(timestep.advance_at(ManualInstant::from_millis(17)).count, 1);
+    assert_eq!(timestep.advance_at(ManualInstant::from_millis(25)).count, 1);
+    assert_eq!(
+        timestep
+            .accumulator()
+            .elapsed_at(ManualInstant::from_millis(25)),
+        Duration::from_millis(5),
+    );
+    assert!((timestep.alpha_at(ManualInstant::from_millis(40)) - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn spiral_of_death() {
+    let mut timestep = timestep();
+    let steps = timestep.advance_at(ManualInstant::from_millis(1_003));
+    assert_eq!(steps.count, 4);
+    assert!((steps.alpha - 0.3).abs() < 1e-9);
+    assert_eq!(
+        timestep.advance_at(ManualInstant::from_millis(1_013)).count,
+        1
+    );
+}
+
+#[test]
+fn paused() {
+    let mut timestep = timestep();
+    timestep
+        .accumulator_mut()
+        .stop_at(ManualInstant::from_millis(15));
+    assert_eq!(
+        timestep.advance_at(ManualInstant::from_millis(100)).count,
+        1
+    );
+    assert_eq!(
+        timestep.advance_at(ManualInstant::from_millis(200)).count,
+        0
+    );
+}
+
+#[test]
+#[should_panic = "fixed step must be non-zero"]
+fn zero_step() {
+    let _ = FixedStep::<ManualInstant>::new(Duration::ZERO, 1);
+}
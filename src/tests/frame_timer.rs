@@ -0,0 +1,72 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+use std::vec::Vec;
+
+use super::manual::ManualInstant;
+
+type FrameTimer = crate::FrameTimer<ManualInstant, 4>;
+
+fn ticked(times: &[u64]) -> FrameTimer {
+    let mut frames = FrameTimer::new();
+    for &time in times {
+        frames.tick_at(ManualInstant::from_millis(time));
+    }
+    frames
+}
+
+#[test]
+fn first_tick() {
+    let mut frames = FrameTimer::new();
+    assert_eq!(frames.tick_at(ManualInstant::from_millis(100)), None);
+    assert!(frames.is_empty());
+    assert_eq!(frames.average(), None);
+    assert_eq!(frames.fps(), None);
+    assert_eq!(
+        frames.tick_at(ManualInstant::from_millis(116)),
+        Some(Duration::from_millis(16))
+    );
+    assert_eq!(frames.last_delta(), Some(Duration::from_millis(16)));
+}
+
+#[test]
+fn statistics() {
+    let frames = ticked(&[0, 10, 30, 40]);
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames.min(), Some(Duration::from_millis(10)));
+    assert_eq!(frames.max(), Some(Duration::from_millis(20)));
+    assert_eq!(frames.average(), Some(Duration::from_nanos(13_333_333)));
+    assert_eq!(frames.jitter(), Some(Duration::from_nanos(4_444_444)));
+
+    let frames = ticked(&[0, 20, 40]);
+    assert!((frames.fps().unwrap() - 50.0).abs() < 1e-9);
+    assert_eq!(frames.jitter(), Some(Duration::ZERO));
+}
+
+#[test]
+fn window_wraps() {
+    let frames = ticked(&[0, 1, 3, 6, 10, 15, 21]);
+    assert_eq!(frames.len(), 4);
+    assert_eq!(frames.capacity(), 4);
+    assert_eq!(
+        frames.deltas().collect::<Vec<_>>(),
+        [
+            Duration::from_millis(3),
+            Duration::from_millis(4),
+            Duration::from_millis(5),
+            Duration::from_millis(6)
+        ],
+    );
+    assert_eq!(frames.last_delta(), Some(Duration::from_millis(6)));
+    assert_eq!(frames.min(), Some(Duration::from_millis(3)));
+}
+
+#[test]
+fn reset() {
+    let mut frames = ticked(&[0, 10, 20]);
+    frames.reset();
+    assert!(frames.is_empty());
+    assert_eq!(frames.tick_at(ManualInstant::from_millis(30)), None);
+}
@@ -0,0 +1,138 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use super::manual::ManualInstant;
+use crate::{Bonus, TimeControl};
+
+type GameClock<'a, const N: usize> = crate::GameClock<'a, ManualInstant, N>;
+
+fn sudden_death(time: u64, bonus: Bonus) -> [TimeControl; 1] {
+    [TimeControl::new(Duration::from_secs(time), None, bonus)]
+}
+
+#[test]
+fn hand_off() {
+    let stages = sudden_death(60, Bonus::Increment(Duration::ZERO));
+    let mut clock = GameClock::<3>::new(&stages);
+    assert_eq!(clock.press_at(ManualInstant::from_secs(0)), None);
+
+    clock.start_at(0, ManualInstant::from_secs(0));
+    assert_eq!(clock.press_at(ManualInstant::from_secs(5)), Some(1));
+    assert_eq!(clock.press_at(ManualInstant::from_secs(12)), Some(2));
+    assert_eq!(clock.press_at(ManualInstant::from_secs(20)), Some(0));
+
+    assert_eq!(
+        clock.remaining_at(0, ManualInstant::from_secs(30)),
+        Duration::from_secs(45)
+    );
+    assert_eq!(
+        clock.remaining_at(1, ManualInstant::from_secs(30)),
+        Duration::from_secs(53)
+    );
+    assert_eq!(
+        clock.remaining_at(2, ManualInstant::from_secs(30)),
+        Duration::from_secs(52)
+    );
+    assert_eq!(clock.moves(0), 1);
+}
+
+#[test]
+fn increment() {
+    let stages = sudden_death(60, Bonus::Increment(Duration::from_secs(2)));
+    let mut clock = GameClock::<2>::new(&stages);
+    clock.start_at(0, ManualInstant::from_secs(0));
+    clock.press_at(ManualInstant::from_secs(5));
+    assert_eq!(
+        clock.remaining_at(0, ManualInstant::from_secs(5)),
+        Duration::from_secs(57)
+    );
+}
+
+#[test]
+fn simple_delay() {
+    let stages = sudden_death(60, Bonus::Delay(Duration::from_secs(3)));
+    let mut clock = GameClock::<2>::new(&stages);
+    clock.start_at(0, ManualInstant::from_secs(0));
+    assert_eq!(
+        clock.remaining_at(0, ManualInstant::from_secs(2)),
+        Duration::from_secs(60)
+    );
+    assert_eq!(clock.flag_instant(), Some(ManualInstant::from_secs(63)));
+
+    // pausing keeps the unused part of the delay
+    clock.pause_at(ManualInstant::from_secs(1));
+    clock.resume_at(ManualInstant::from_secs(10));
+    assert_eq!(
+        clock.remaining_at(0, ManualInstant::from_secs(12)),
+        Duration::from_secs(60)
+    );
+    assert_eq!(
+        clock.remaining_at(0, ManualInstant::from_secs(15)),
+        Duration::from_secs(57)
+    );
+
+    clock.press_at(ManualInstant::from_secs(15));
+    clock.press_at(ManualInstant::from_secs(17));
+    assert_eq!(
+        clock.remaining_at(1, ManualInstant::from_secs(17)),
+        Duration::from_secs(60)
+    );
+}
+
+#[test]
+fn bronstein_delay() {
+    let stages = sudden_death(60, Bonus::Bronstein(Duration::from_secs(3)));
+    let mut clock = GameClock::<2>::new(&stages);
+    clock.start_at(0, ManualInstant::from_secs(0));
+    clock.press_at(ManualInstant::from_secs(2));
+    clock.press_at(ManualInstant::from_secs(10));
+    assert_eq!(
+        clock.remaining_at(0, ManualInstant::from_secs(10)),
+        Duration::from_secs(60)
+    );
+    assert_eq!(
+        clock.remaining_at(1, ManualInstant::from_secs(10)),
+        Duration::from_secs(55)
+    );
+}
+
+#[test]
+fn stages_and_flag() {
+    let stages = [
+        TimeControl::new(
+            Duration::from_secs(10),
+            Some(2),
+            Bonus::Increment(Duration::ZERO),
+        ),
+        TimeControl::new(
+            Duration::from_secs(5),
+            Some(1),
+            Bonus::Increment(Duration::ZERO),
+        ),
+    ];
+    let mut clock = GameClock::<1>::new(&stages);
+    clock.start_at(0, ManualInstant::from_secs(0));
+    clock.press_at(ManualInstant::from_secs(1));
+    assert_eq!(clock.stage(0), &stages[0]);
+    clock.press_at(ManualInstant::from_secs(2));
+    assert_eq!(clock.stage(0), &stages[1]);
+    assert_eq!(
+        clock.remaining_at(0, ManualInstant::from_secs(2)),
+        Duration::from_secs(13)
+    );
+
+    // the last stage repeats
+    clock.press_at(ManualInstant::from_secs(3));
+    assert_eq!(
+        clock.remaining_at(0, ManualInstant::from_secs(3)),
+        Duration::from_secs(17)
+    );
+
+    assert_eq!(clock.flagged_at(ManualInstant::from_secs(19)), None);
+    assert_eq!(clock.flagged_at(ManualInstant::from_secs(20)), Some(0));
+    assert_eq!(clock.press_at(ManualInstant::from_secs(21)), None);
+    assert_eq!(clock.moves(0), 3);
+}
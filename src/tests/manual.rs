@@ -0,0 +1,56 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::cell::Cell;
+use core::time::Duration;
+
+use crate::Instant;
+
+thread_local! {
+    static NOW: Cell<ManualInstant> = const { Cell::new(ManualInstant::EPOCH) };
+}
+
+/// A deterministic [`Instant`] which only moves when told to. Each thread has
+/// its own clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ManualInstant(pub Duration);
+
+impl ManualInstant {
+    /// The earliest representable instant.
+    pub const EPOCH: Self = Self(Duration::ZERO);
+
+    /// Returns the instant `secs` seconds after [`EPOCH`](Self::EPOCH).
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(Duration::from_secs(secs))
+    }
+
+    /// Returns the instant `millis` milliseconds after
+    /// [`EPOCH`](Self::EPOCH).
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(Duration::from_millis(millis))
+    }
+
+    /// Moves the current thread's clock ahead by `dur`.
+    pub fn advance(dur: Duration) {
+        NOW.with(|now| now.set(Self(now.get().0 + dur)));
+    }
+}
+
+impl Instant for ManualInstant {
+    fn now() -> Self {
+        NOW.with(Cell::get)
+    }
+
+    fn checked_add(&self, duration: Duration) -> Option<Self> {
+        self.0.checked_add(duration).map(Self)
+    }
+
+    fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        self.0.checked_sub(duration).map(Self)
+    }
+
+    fn saturating_duration_since(&self, earlier: Self) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+}
@@ -0,0 +1,190 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::fmt::Write;
+use core::time::Duration;
+use metrics::{
+    Counter, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use std::string::String;
+use std::sync::{Arc, Mutex};
+use std::vec::Vec;
+
+use super::manual::ManualInstant;
+use super::DELAY;
+use crate::{HistogramGuard, StopwatchRegistry};
+
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+/// Records every value set or recorded as `(key name, value)`.
+#[derive(Clone, Default)]
+struct Log(Arc<Mutex<Vec<(String, f64)>>>);
+
+struct Handle {
+    name: String,
+    log: Log,
+}
+
+impl Handle {
+    fn push(&self, value: f64) {
+        self.log.0.lock().unwrap().push((self.name.clone(), value));
+    }
+}
+
+impl GaugeFn for Handle {
+    // the registry only sets gauges
+    fn increment(&self, _value: f64) {}
+
+    fn decrement(&self, _value: f64) {}
+
+    fn set(&self, value: f64) {
+        self.push(value);
+    }
+}
+
+impl HistogramFn for Handle {
+    fn record(&self, value: f64) {
+        self.push(value);
+    }
+}
+
+impl Log {
+    fn handle(&self, key: &Key) -> Arc<Handle> {
+        let mut name = String::from(key.name());
+        for label in key.labels() {
+            write!(name, ",{}={}", label.key(), label.value()).unwrap();
+        }
+        Arc::new(Handle {
+            name,
+            log: self.clone(),
+        })
+    }
+
+    fn take(&self) -> Vec<(String, f64)> {
+        core::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl Recorder for Log {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, _: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::noop()
+    }
+
+    fn register_gauge(&self, key: &Key, metadata: &Metadata<'_>) -> Gauge {
+        assert_eq!(metadata.target(), "libsw_core");
+        Gauge::from_arc(self.handle(key))
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(self.handle(key))
+    }
+}
+
+#[test]
+fn record_elapsed() {
+    let log = Log::default();
+    let histogram = Histogram::from_arc(log.handle(&Key::from_static_name("h")));
+
+    let sw = Stopwatch::from_raw(Duration::from_secs(1), Some(ManualInstant::from_secs(1)));
+    sw.record_elapsed_at(&histogram, ManualInstant::from_secs(3));
+    Stopwatch::with_elapsed(DELAY).record_elapsed(&histogram);
+
+    assert_eq!(log.take(), [("h".into(), 3.0), ("h".into(), 0.1)]);
+}
+
+#[test]
+fn record_lap() {
+    let log = Log::default();
+    let histogram = Histogram::from_arc(log.handle(&Key::from_static_name("h")));
+
+    let mut sw = Stopwatch::new_started_at(ManualInstant::from_secs(0));
+    assert_eq!(
+        sw.record_lap_at(&histogram, ManualInstant::from_secs(2)),
+        Duration::from_secs(2)
+    );
+    assert_eq!(
+        sw.record_lap_at(&histogram, ManualInstant::from_secs(3)),
+        Duration::from_secs(1)
+    );
+    assert!(sw.is_running());
+    assert_eq!(sw.elapsed_at(ManualInstant::from_secs(3)), Duration::ZERO);
+
+    assert_eq!(log.take(), [("h".into(), 2.0), ("h".into(), 1.0)]);
+}
+
+#[test]
+fn guard() {
+    let log = Log::default();
+    let histogram = Histogram::from_arc(log.handle(&Key::from_static_name("h")));
+
+    {
+        let mut guard = HistogramGuard::<ManualInstant>::new(histogram.clone());
+        ManualInstant::advance(DELAY);
+        guard.stopwatch().stop();
+        ManualInstant::advance(DELAY); // excluded
+        guard.stopwatch().start();
+        ManualInstant::advance(DELAY);
+    }
+    assert_eq!(log.take(), [("h".into(), 0.2)]);
+
+    let guard = HistogramGuard::new_started_at(histogram.clone(), ManualInstant::from_secs(1));
+    assert_eq!(
+        guard.record_at(ManualInstant::from_secs(5)),
+        Duration::from_secs(4)
+    );
+    assert_eq!(log.take(), [("h".into(), 4.0)]);
+
+    HistogramGuard::<ManualInstant>::new(histogram).disarm();
+    assert_eq!(log.take(), []);
+}
+
+#[test]
+fn registry() {
+    let mut registry = StopwatchRegistry::new();
+    assert!(registry.is_empty());
+
+    assert_eq!(
+        registry.insert("stopped", Stopwatch::with_elapsed(DELAY)),
+        None
+    );
+    registry.insert(
+        ("running", &[("id", "1")]),
+        Stopwatch::new_started_at(ManualInstant::from_secs(1)),
+    );
+    registry.insert("removed", Stopwatch::new());
+    assert_eq!(registry.len(), 3);
+
+    assert_eq!(registry.remove("removed"), Some(Stopwatch::new()));
+    assert_eq!(registry.remove("removed"), None);
+    assert_eq!(registry.get("running"), None); // labels are part of the key
+    assert!(registry
+        .get(("running", &[("id", "1")]))
+        .unwrap()
+        .is_running());
+
+    let old = registry.insert("stopped", Stopwatch::with_elapsed(Duration::from_secs(2)));
+    assert_eq!(old, Some(Stopwatch::with_elapsed(DELAY)));
+    registry
+        .get_mut("stopped")
+        .unwrap()
+        .start_at(ManualInstant::from_secs(2));
+
+    let log = Log::default();
+    metrics::with_local_recorder(&log, || registry.flush_at(ManualInstant::from_secs(4)));
+    assert_eq!(
+        log.take(),
+        [("stopped".into(), 4.0), ("running,id=1".into(), 3.0)]
+    );
+
+    let keys: Vec<_> = registry.iter().map(|(key, _)| key.name()).collect();
+    assert_eq!(keys, ["stopped", "running"]);
+    assert_eq!(registry.iter().rev().len(), 2);
+}
@@ -0,0 +1,136 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use super::manual::ManualInstant;
+
+type MultiPauseStopwatch = crate::MultiPauseStopwatch<ManualInstant, Reason, 3>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Reason {
+    Unfocused,
+    Modal,
+    Stall,
+    Other,
+}
+
+#[test]
+fn overlapping_reasons() {
+    let mut sw = MultiPauseStopwatch::new_started_at(ManualInstant::from_secs(0));
+    assert!(sw.pause_at(Reason::Unfocused, ManualInstant::from_secs(1)));
+    assert!(sw.pause_at(Reason::Stall, ManualInstant::from_secs(2)));
+    assert_eq!(sw.active_reasons(), 2);
+
+    assert!(sw.resume_at(Reason::Unfocused, ManualInstant::from_secs(3)));
+    assert!(sw.is_paused());
+    assert!(!sw.is_paused_by(Reason::Unfocused));
+    assert!(sw.is_paused_by(Reason::Stall));
+
+    assert!(sw.resume_at(Reason::Stall, ManualInstant::from_secs(5)));
+    assert!(sw.is_running());
+    assert_eq!(
+        sw.elapsed_at(ManualInstant::from_secs(6)),
+        Duration::from_secs(2)
+    );
+    assert_eq!(
+        sw.paused_time_at(Reason::Unfocused, ManualInstant::from_secs(6)),
+        Duration::from_secs(2)
+    );
+    assert_eq!(
+        sw.paused_time_at(Reason::Stall, ManualInstant::from_secs(6)),
+        Duration::from_secs(3)
+    );
+    assert_eq!(
+        sw.paused_time_at(Reason::Modal, ManualInstant::from_secs(6)),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn repeated_pause_and_resume() {
+    let mut sw = MultiPauseStopwatch::new_started_at(ManualInstant::from_secs(0));
+    assert!(sw.pause_at(Reason::Modal, ManualInstant::from_secs(1)));
+    assert!(!sw.pause_at(Reason::Modal, ManualInstant::from_secs(2)));
+    assert!(!sw.resume_at(Reason::Unfocused, ManualInstant::from_secs(3)));
+    assert!(sw.is_paused());
+
+    assert!(sw.resume_at(Reason::Modal, ManualInstant::from_secs(4)));
+    assert!(!sw.resume_at(Reason::Modal, ManualInstant::from_secs(5)));
+    assert_eq!(
+        sw.elapsed_at(ManualInstant::from_secs(5)),
+        Duration::from_secs(2)
+    );
+    assert_eq!(
+        sw.paused_time_at(Reason::Modal, ManualInstant::from_secs(5)),
+        Duration::from_secs(3)
+    );
+}
+
+#[test]
+fn resumes_at_anchor() {
+    let mut sw = MultiPauseStopwatch::new_started_at(ManualInstant::from_secs(0));
+    sw.pause_at(Reason::Unfocused, ManualInstant::from_secs(1));
+    sw.resume_at(Reason::Unfocused, ManualInstant::from_secs(10));
+    assert_eq!(sw.stopwatch().start, Some(ManualInstant::from_secs(10)));
+}
+
+#[test]
+fn capacity() {
+    let mut sw = MultiPauseStopwatch::new_started_at(ManualInstant::from_secs(0));
+    assert!(sw.pause_at(Reason::Unfocused, ManualInstant::from_secs(1)));
+    assert!(sw.pause_at(Reason::Modal, ManualInstant::from_secs(1)));
+    assert!(sw.pause_at(Reason::Stall, ManualInstant::from_secs(1)));
+    assert!(sw.resume_at(Reason::Stall, ManualInstant::from_secs(2)));
+
+    // every slot is taken, even by an inactive reason
+    assert!(!sw.pause_at(Reason::Other, ManualInstant::from_secs(3)));
+    assert!(!sw.resume_at(Reason::Other, ManualInstant::from_secs(4)));
+    assert!(!sw.is_paused_by(Reason::Other));
+    assert_eq!(sw.active_reasons(), 2);
+    assert_eq!(
+        sw.paused_time_at(Reason::Other, ManualInstant::from_secs(5)),
+        Duration::ZERO
+    );
+    assert_eq!(
+        sw.paused_time_at(Reason::Stall, ManualInstant::from_secs(5)),
+        Duration::from_secs(1)
+    );
+
+    // a tracked reason can pause again
+    assert!(sw.pause_at(Reason::Stall, ManualInstant::from_secs(6)));
+    assert_eq!(sw.active_reasons(), 3);
+}
+
+#[test]
+fn start_and_stop() {
+    let mut sw = MultiPauseStopwatch::new();
+    assert!(!sw.is_started());
+    assert!(!sw.is_running());
+    assert!(!sw.is_paused());
+    assert!(!sw.stop_at(ManualInstant::from_secs(0)));
+
+    // paused while stopped, so starting doesn't run it yet
+    assert!(sw.pause_at(Reason::Modal, ManualInstant::from_secs(1)));
+    assert!(!sw.resume_at(Reason::Stall, ManualInstant::from_secs(1)));
+    assert!(sw.start_at(ManualInstant::from_secs(2)));
+    assert!(!sw.start_at(ManualInstant::from_secs(2)));
+    assert!(!sw.is_running());
+    assert!(sw.resume_at(Reason::Modal, ManualInstant::from_secs(3)));
+    assert!(sw.is_running());
+
+    // stopped while paused, so resuming doesn't run it again
+    assert!(sw.pause_at(Reason::Unfocused, ManualInstant::from_secs(5)));
+    assert!(sw.stop_at(ManualInstant::from_secs(6)));
+    assert!(sw.resume_at(Reason::Unfocused, ManualInstant::from_secs(7)));
+    assert!(!sw.is_running());
+    assert_eq!(
+        sw.elapsed_at(ManualInstant::from_secs(8)),
+        Duration::from_secs(2)
+    );
+    assert_eq!(
+        sw.paused_time_at(Reason::Modal, ManualInstant::from_secs(8)),
+        Duration::from_secs(2)
+    );
+}
@@ -0,0 +1,168 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+use std::string::String;
+
+use super::manual::ManualInstant;
+use crate::OpenMetrics;
+
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+#[test]
+fn golden() {
+    let timers = [
+        ("idle", Stopwatch::new()),
+        (
+            "build",
+            Stopwatch::with_elapsed(Duration::from_millis(1500)),
+        ),
+        (
+            "deploy",
+            Stopwatch::from_raw(
+                Duration::from_secs(2),
+                Some(ManualInstant::from_millis(250)),
+            ),
+        ),
+        (
+            "odd \"name\"\\\n",
+            Stopwatch::with_elapsed(Duration::from_nanos(1)),
+        ),
+    ];
+
+    let mut text = String::new();
+    OpenMetrics::new("ci", &timers)
+        .buckets(&[
+            Duration::ZERO,
+            Duration::from_millis(1500),
+            Duration::from_secs(60),
+        ])
+        .write_at(&mut text, ManualInstant::from_millis(1000))
+        .unwrap();
+
+    assert_eq!(
+        text,
+        r#"# TYPE ci_seconds gauge
+# UNIT ci_seconds seconds
+# HELP ci_seconds Total time elapsed.
+ci_seconds{timer="idle"} 0.0
+ci_seconds{timer="build"} 1.5
+ci_seconds{timer="deploy"} 2.75
+ci_seconds{timer="odd \"name\"\\\n"} 0.000000001
+# TYPE ci_running gauge
+# HELP ci_running Whether the timer is running.
+ci_running{timer="idle"} 0
+ci_running{timer="build"} 0
+ci_running{timer="deploy"} 1
+ci_running{timer="odd \"name\"\\\n"} 0
+# TYPE ci_elapsed_seconds gaugehistogram
+# UNIT ci_elapsed_seconds seconds
+# HELP ci_elapsed_seconds Distribution of total time elapsed across timers.
+ci_elapsed_seconds_bucket{le="0.0"} 1
+ci_elapsed_seconds_bucket{le="1.5"} 3
+ci_elapsed_seconds_bucket{le="60.0"} 4
+ci_elapsed_seconds_bucket{le="+Inf"} 4
+ci_elapsed_seconds_gcount 4
+ci_elapsed_seconds_gsum 4.250000001
+# EOF
+"#
+    );
+}
+
+#[test]
+fn golden_without_buckets() {
+    let timers = [(
+        "a",
+        Stopwatch::new_started_at(ManualInstant::from_millis(0)),
+    )];
+
+    let mut text = String::new();
+    OpenMetrics::new("app_timer", &timers)
+        .write_families_at(&mut text, ManualInstant::from_millis(20))
+        .unwrap();
+
+    assert_eq!(
+        text,
+        r#"# TYPE app_timer_seconds gauge
+# UNIT app_timer_seconds seconds
+# HELP app_timer_seconds Total time elapsed.
+app_timer_seconds{timer="a"} 0.02
+# TYPE app_timer_running gauge
+# HELP app_timer_running Whether the timer is running.
+app_timer_running{timer="a"} 1
+"#
+    );
+}
+
+#[test]
+fn empty() {
+    let mut text = String::new();
+    OpenMetrics::<ManualInstant>::new("x", &[])
+        .buckets(&[])
+        .write(&mut text)
+        .unwrap();
+
+    assert_eq!(
+        text,
+        r#"# TYPE x_seconds gauge
+# UNIT x_seconds seconds
+# HELP x_seconds Total time elapsed.
+# TYPE x_running gauge
+# HELP x_running Whether the timer is running.
+# TYPE x_elapsed_seconds gaugehistogram
+# UNIT x_elapsed_seconds seconds
+# HELP x_elapsed_seconds Distribution of total time elapsed across timers.
+x_elapsed_seconds_bucket{le="+Inf"} 0
+x_elapsed_seconds_gcount 0
+x_elapsed_seconds_gsum 0.0
+# EOF
+"#
+    );
+}
+
+#[test]
+fn saturates() {
+    let timers = [(
+        "max",
+        Stopwatch::from_raw(Duration::MAX, Some(ManualInstant::from_millis(0))),
+    )];
+
+    let mut text = String::new();
+    OpenMetrics::new("x", &timers)
+        .write_families_at(&mut text, ManualInstant::from_millis(1))
+        .unwrap();
+    assert!(text.contains("x_seconds{timer=\"max\"} 18446744073709551615.999999999\n"));
+}
+
+#[test]
+fn scrape_across_reset() {
+    let mut timers = [("a", Stopwatch::with_elapsed(Duration::from_secs(3)))];
+    let buckets = [Duration::from_secs(1)];
+
+    let mut before = String::new();
+    OpenMetrics::new("x", &timers)
+        .buckets(&buckets)
+        .write_families_at(&mut before, ManualInstant::EPOCH)
+        .unwrap();
+
+    timers[0].1.reset();
+    let mut after = String::new();
+    OpenMetrics::new("x", &timers)
+        .buckets(&buckets)
+        .write_families_at(&mut after, ManualInstant::EPOCH)
+        .unwrap();
+
+    for text in [&before, &after] {
+        assert!(text.contains("# TYPE x_seconds gauge\n"));
+        assert!(text.contains("# TYPE x_elapsed_seconds gaugehistogram\n"));
+        assert!(!text.contains("counter"));
+        assert!(!text.contains(" histogram"));
+    }
+    assert!(before.contains("x_seconds{timer=\"a\"} 3.0\n"));
+    assert!(before.contains("x_elapsed_seconds_bucket{le=\"1.0\"} 0\n"));
+    assert!(before.contains("x_elapsed_seconds_gsum 3.0\n"));
+    assert!(after.contains("x_seconds{timer=\"a\"} 0.0\n"));
+    assert!(after.contains("x_elapsed_seconds_bucket{le=\"1.0\"} 1\n"));
+    assert!(after.contains("x_elapsed_seconds_gsum 0.0\n"));
+}
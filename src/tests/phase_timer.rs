@@ -0,0 +1,116 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+use std::vec::Vec;
+
+use super::manual::ManualInstant;
+use crate::{Phase, PhaseBoundary};
+
+type PhaseTimer<'a> = crate::PhaseTimer<'a, ManualInstant>;
+
+const PHASES: [Duration; 2] = [Duration::from_secs(3), Duration::from_secs(1)];
+
+#[test]
+fn phase() {
+    let timer = PhaseTimer::new_started_at(&PHASES, Some(2), ManualInstant::from_secs(0));
+    assert_eq!(
+        timer.phase_at(ManualInstant::from_secs(1)),
+        Some(Phase {
+            cycle: 0,
+            index: 0,
+            elapsed: Duration::from_secs(1),
+            remaining: Duration::from_secs(2),
+        }),
+    );
+    assert_eq!(
+        timer
+            .phase_at(ManualInstant::from_secs(3))
+            .map(|phase| phase.index),
+        Some(1)
+    );
+    assert_eq!(
+        timer
+            .phase_at(ManualInstant::from_secs(6))
+            .map(|phase| phase.cycle),
+        Some(1)
+    );
+    assert_eq!(timer.cycles_done_at(ManualInstant::from_secs(6)), 1);
+
+    assert!(!timer.is_finished_at(ManualInstant::from_secs(7)));
+    assert!(timer.is_finished_at(ManualInstant::from_secs(8)));
+    assert_eq!(timer.cycles_done_at(ManualInstant::from_secs(100)), 2);
+}
+
+#[test]
+fn pause() {
+    let mut timer = PhaseTimer::new_started_at(&PHASES, None, ManualInstant::from_secs(0));
+    timer.stop_at(ManualInstant::from_secs(2));
+    assert_eq!(
+        timer
+            .phase_at(ManualInstant::from_secs(50))
+            .map(|phase| phase.remaining),
+        Some(Duration::from_secs(1))
+    );
+    timer.start_at(ManualInstant::from_secs(50));
+    assert_eq!(
+        timer
+            .phase_at(ManualInstant::from_secs(51))
+            .map(|phase| phase.index),
+        Some(1)
+    );
+    assert_eq!(timer.cycles_done_at(ManualInstant::from_secs(1000)), 238);
+}
+
+#[test]
+fn boundaries() {
+    let mut timer = PhaseTimer::new_started_at(&PHASES, Some(2), ManualInstant::from_secs(0));
+    timer.stop_at(ManualInstant::from_secs(1));
+    assert_eq!(
+        timer.boundaries_at(ManualInstant::from_secs(1)).next(),
+        None
+    );
+
+    timer.start_at(ManualInstant::from_secs(10));
+    let instants = |timer: &PhaseTimer, anchor| {
+        timer
+            .boundaries_at(anchor)
+            .map(|boundary| boundary.instant)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        instants(&timer, ManualInstant::from_secs(11)),
+        [
+            ManualInstant::from_secs(12),
+            ManualInstant::from_secs(13),
+            ManualInstant::from_secs(16),
+            ManualInstant::from_secs(17)
+        ]
+    );
+    assert_eq!(
+        instants(&timer, ManualInstant::from_secs(13)),
+        [ManualInstant::from_secs(16), ManualInstant::from_secs(17)]
+    );
+    assert_eq!(
+        timer.boundaries_at(ManualInstant::from_secs(13)).next(),
+        Some(PhaseBoundary {
+            instant: ManualInstant::from_secs(16),
+            cycle: 1,
+            index: 0,
+        }),
+    );
+}
+
+#[test]
+fn boundaries_end_on_overflow() {
+    let end = ManualInstant(Duration::MAX.saturating_sub(Duration::from_secs(10)));
+    let timer = PhaseTimer::new_started_at(&PHASES, None, end);
+    assert_eq!(timer.boundaries_at(end).count(), 4);
+}
+
+#[test]
+#[should_panic = "phases must have a non-zero duration"]
+fn empty_phases() {
+    let _ = PhaseTimer::new(&[Duration::ZERO], None);
+}
@@ -0,0 +1,66 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::cell::Cell;
+use core::time::Duration;
+
+use super::manual::ManualInstant;
+use crate::{probe, probe_with, Instant};
+
+thread_local! {
+    static READINGS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A clock which steps forward by 3ns and back by 1ns on alternate readings.
+#[derive(Clone, Copy, Debug)]
+struct Zigzag(ManualInstant);
+
+impl Instant for Zigzag {
+    fn now() -> Self {
+        let n = READINGS.with(|readings| readings.replace(readings.get() + 1));
+        Self(ManualInstant(Duration::from_nanos(n / 2 * 2 + n % 2 * 3)))
+    }
+
+    fn checked_add(&self, duration: Duration) -> Option<Self> {
+        self.0.checked_add(duration).map(Self)
+    }
+
+    fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        self.0.checked_sub(duration).map(Self)
+    }
+
+    fn saturating_duration_since(&self, earlier: Self) -> Duration {
+        self.0.saturating_duration_since(earlier.0)
+    }
+}
+
+#[test]
+fn std_instant() {
+    let report = probe::<std::time::Instant>();
+    assert_eq!(report.samples, 10_000);
+    assert!(report.is_monotonic());
+}
+
+#[test]
+fn stopped_clock() {
+    let report = probe_with::<ManualInstant>(100);
+    assert_eq!(report.resolution, None);
+    assert_eq!(report.repeats, 99);
+    assert!(report.is_monotonic());
+}
+
+#[test]
+fn backward_steps() {
+    let report = probe_with::<Zigzag>(101);
+    assert_eq!(report.backward_steps, 50);
+    assert_eq!(report.resolution, Some(Duration::from_nanos(3)));
+    assert_eq!(report.repeats, 0);
+    assert!(!report.is_monotonic());
+}
+
+#[test]
+#[should_panic = "probe needs at least two samples"]
+fn too_few_samples() {
+    let _ = probe_with::<ManualInstant>(1);
+}
@@ -0,0 +1,114 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use super::{DELAY, I};
+use crate::Instant;
+
+type Profiler = crate::Profiler<I>;
+
+fn anchors<const N: usize>() -> [I; N] {
+    let mut anchors = [I::now(); N];
+    for i in 1..N {
+        anchors[i] = Instant::checked_add(&anchors[i - 1], DELAY).unwrap();
+    }
+    anchors
+}
+
+#[test]
+fn exit_without_enter() {
+    let mut profiler = Profiler::new();
+    assert!(profiler.is_idle());
+    assert_eq!(profiler.exit(), None);
+    assert_eq!(profiler.roots().len(), 0);
+}
+
+#[test]
+fn inclusive_and_exclusive() {
+    let [t0, t1, t2, t3, t4] = anchors();
+    let mut profiler = Profiler::new();
+
+    profiler.enter_at("compile", t0);
+    profiler.enter_at("parse", t0);
+    assert_eq!(profiler.exit_at(t1), Some(DELAY));
+    profiler.enter_at("typecheck", t1);
+    assert_eq!(profiler.exit_at(t2), Some(DELAY));
+    profiler.enter_at("codegen", t2);
+    assert_eq!(profiler.exit_at(t3), Some(DELAY));
+    assert_eq!(profiler.depth(), 1);
+    assert_eq!(profiler.exit_at(t4), Some(DELAY * 4));
+    assert!(profiler.is_idle());
+
+    let compile = profiler.find(&["compile"]).unwrap();
+    assert_eq!(compile.calls(), 1);
+    assert_eq!(compile.inclusive(), DELAY * 4);
+    assert_eq!(compile.exclusive(), DELAY);
+    assert_eq!(compile.children().len(), 3);
+
+    for child in compile.children() {
+        assert_eq!(child.calls(), 1);
+        assert_eq!(child.inclusive(), DELAY);
+        assert_eq!(child.exclusive(), DELAY);
+        assert_eq!(child.parent().unwrap().name(), "compile");
+    }
+}
+
+#[test]
+fn repeated_paths_aggregate() {
+    let [t0, t1, t2, t3] = anchors();
+    let mut profiler = Profiler::new();
+
+    for (start, end) in [(t0, t1), (t2, t3)] {
+        profiler.enter_at("frame", start);
+        profiler.enter_at("draw", start);
+        profiler.exit_at(end);
+        profiler.exit_at(end);
+    }
+    profiler.enter_at("draw", t0);
+    profiler.exit_at(t3);
+
+    let roots: Vec<_> = profiler.roots().map(|node| node.name()).collect();
+    assert_eq!(roots, ["frame", "draw"]);
+
+    let draw = profiler.find(&["frame", "draw"]).unwrap();
+    assert_eq!(draw.calls(), 2);
+    assert_eq!(draw.inclusive(), DELAY * 2);
+    assert_eq!(
+        profiler.find(&["frame"]).unwrap().exclusive(),
+        Duration::ZERO
+    );
+
+    // same name, different path
+    let draw = profiler.find(&["draw"]).unwrap();
+    assert_eq!(draw.calls(), 1);
+    assert_eq!(draw.inclusive(), DELAY * 3);
+    assert!(draw.parent().is_none());
+}
+
+#[test]
+fn recursion_nests() {
+    let [t0, t1] = anchors();
+    let mut profiler = Profiler::new();
+
+    profiler.enter_at("eval", t0);
+    profiler.enter_at("eval", t0);
+    profiler.exit_at(t1);
+    profiler.exit_at(t1);
+
+    assert!(profiler.find(&["eval", "eval"]).is_some());
+    assert!(profiler.find(&["eval", "eval", "eval"]).is_none());
+    assert!(profiler.find(&[]).is_none());
+}
+
+#[test]
+fn reset() {
+    let mut profiler = Profiler::new();
+    profiler.enter("a");
+    profiler.exit();
+    profiler.enter("b");
+    profiler.reset();
+    assert!(profiler.is_idle());
+    assert!(profiler.find(&["a"]).is_none());
+}
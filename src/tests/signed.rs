@@ -0,0 +1,160 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::hash::{Hash, Hasher};
+use core::time::Duration;
+use std::collections::hash_map::DefaultHasher;
+
+use super::manual::ManualInstant;
+use crate::SignedDuration;
+
+type SignedStopwatch = crate::SignedStopwatch<ManualInstant>;
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+fn pos(secs: u64) -> SignedDuration {
+    SignedDuration::positive(Duration::from_secs(secs))
+}
+
+fn neg(secs: u64) -> SignedDuration {
+    SignedDuration::negative(Duration::from_secs(secs))
+}
+
+fn hash(sw: SignedStopwatch) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    sw.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn duration_arithmetic() {
+    assert_eq!(neg(0), SignedDuration::ZERO);
+    assert!(!neg(0).is_negative());
+    assert_eq!(pos(3).checked_add(neg(5)), Some(neg(2)));
+    assert_eq!(neg(3).checked_sub(neg(5)), Some(pos(2)));
+    assert_eq!(neg(3).checked_add(pos(3)), Some(SignedDuration::ZERO));
+    assert_eq!(-pos(4), neg(4));
+
+    assert_eq!(SignedDuration::MAX.checked_add(pos(1)), None);
+    assert_eq!(
+        SignedDuration::MIN.saturating_sub(pos(1)),
+        SignedDuration::MIN
+    );
+    assert_eq!(
+        SignedDuration::MAX
+            .checked_add(neg(1))
+            .map(|d| d.is_positive()),
+        Some(true)
+    );
+}
+
+#[test]
+fn duration_ordering() {
+    let mut durations = [pos(2), neg(1), SignedDuration::ZERO, neg(5), pos(1)];
+    durations.sort();
+    assert_eq!(
+        durations,
+        [neg(5), neg(1), SignedDuration::ZERO, pos(1), pos(2)]
+    );
+    assert_eq!(Duration::try_from(pos(7)), Ok(Duration::from_secs(7)));
+    assert_eq!(Duration::try_from(neg(7)), Err(neg(7)));
+}
+
+#[test]
+fn counts_through_zero() {
+    let mut sw = SignedStopwatch::new().saturating_sub(pos(30));
+    assert_eq!(sw.elapsed, neg(30));
+
+    sw.start_at(ManualInstant::from_secs(100));
+    assert_eq!(sw.elapsed_at(ManualInstant::from_secs(110)), neg(20));
+    assert_eq!(
+        sw.elapsed_at(ManualInstant::from_secs(130)),
+        SignedDuration::ZERO
+    );
+    sw.stop_at(ManualInstant::from_secs(145));
+    assert_eq!(sw.elapsed, pos(15));
+    assert!(sw.is_stopped());
+
+    sw.toggle_at(ManualInstant::from_secs(200));
+    assert_eq!(sw.start, Some(ManualInstant::from_secs(200)));
+    sw.reset_in_place_at(ManualInstant::from_secs(300));
+    assert_eq!(sw.elapsed_at(ManualInstant::from_secs(301)), pos(1));
+}
+
+#[test]
+fn overflow() {
+    let mut sw = SignedStopwatch::from_raw(SignedDuration::MAX, Some(ManualInstant::from_secs(0)));
+    assert_eq!(sw.checked_elapsed_at(ManualInstant::from_secs(1)), None);
+    assert!(!sw.checked_stop_at(ManualInstant::from_secs(1)));
+    assert!(sw.is_running());
+    sw.stop_at(ManualInstant::from_secs(1));
+    assert_eq!(sw.elapsed, SignedDuration::MAX);
+    assert!(sw.checked_add(pos(1)).is_none());
+}
+
+#[test]
+fn conversions() {
+    let sw = Stopwatch::from_raw(Duration::from_secs(4), Some(ManualInstant::from_secs(10)));
+    let signed = SignedStopwatch::from(sw);
+    assert_eq!(signed.elapsed_at(ManualInstant::from_secs(12)), pos(6));
+
+    let back = Stopwatch::try_from(signed).unwrap();
+    assert_eq!(back.elapsed, sw.elapsed);
+    assert_eq!(back.start, sw.start);
+
+    let debt = signed.saturating_sub(pos(5));
+    let err = Stopwatch::try_from(debt).unwrap_err();
+    assert_eq!(err.elapsed, neg(1));
+}
+
+#[test]
+fn eq_and_hash() {
+    // all of these reach zero at 15 seconds
+    let counting_up = SignedStopwatch::from_raw(neg(5), Some(ManualInstant::from_secs(10)));
+    let counted_up = SignedStopwatch::from_raw(pos(5), Some(ManualInstant::from_secs(20)));
+    let at_zero = SignedStopwatch::new_started_at(ManualInstant::from_secs(15));
+    assert_eq!(counting_up, counted_up);
+    assert_eq!(counting_up, at_zero);
+    assert_eq!(hash(counting_up), hash(counted_up));
+    assert_eq!(hash(counting_up), hash(at_zero));
+    assert_ne!(
+        counting_up,
+        SignedStopwatch::new_started_at(ManualInstant::from_secs(16))
+    );
+
+    assert_eq!(
+        SignedStopwatch::with_elapsed(neg(5)),
+        SignedStopwatch::with_elapsed(neg(5))
+    );
+    assert_ne!(
+        SignedStopwatch::with_elapsed(neg(5)),
+        SignedStopwatch::with_elapsed(pos(5))
+    );
+    assert_ne!(SignedStopwatch::with_elapsed(pos(5)), counted_up);
+
+    let sw = Stopwatch::from_raw(Duration::from_secs(4), Some(ManualInstant::from_secs(10)));
+    assert_eq!(
+        SignedStopwatch::from(sw),
+        SignedStopwatch::from(sw.canonicalize())
+    );
+}
+
+#[test]
+fn unbounded_eq() {
+    // positive elapsed time can't be moved back past the epoch
+    let sw_1 = SignedStopwatch::from_raw(pos(30), Some(ManualInstant::from_secs(10)));
+    let sw_2 = SignedStopwatch::from_raw(pos(25), Some(ManualInstant::from_secs(5)));
+    let sw_3 = SignedStopwatch::from_raw(pos(30), Some(ManualInstant::from_secs(5)));
+    assert_eq!(sw_1, sw_2);
+    assert_eq!(hash(sw_1), hash(sw_2));
+    assert_ne!(sw_1, sw_3);
+
+    // negative elapsed time can't be moved forward past the last instant
+    let before_max = |secs| ManualInstant(Duration::MAX.saturating_sub(Duration::from_secs(secs)));
+    let sw_1 = SignedStopwatch::from_raw(neg(30), Some(before_max(10)));
+    let sw_2 = SignedStopwatch::from_raw(neg(25), Some(before_max(5)));
+    let sw_3 = SignedStopwatch::from_raw(neg(30), Some(before_max(5)));
+    assert_eq!(sw_1, sw_2);
+    assert_eq!(hash(sw_1), hash(sw_2));
+    assert_ne!(sw_1, sw_3);
+}
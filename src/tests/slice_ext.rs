@@ -0,0 +1,184 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+use std::vec::Vec;
+
+use super::manual::ManualInstant;
+use crate::{StopwatchIterExt, StopwatchSliceExt};
+
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+#[test]
+fn one_anchor() {
+    let mut stopwatches = [
+        Stopwatch::new(),
+        Stopwatch::with_elapsed(Duration::from_secs(2)),
+    ];
+    stopwatches.start_all_at(ManualInstant::from_secs(10));
+    assert!(stopwatches
+        .iter()
+        .all(|sw| sw.start == Some(ManualInstant::from_secs(10))));
+
+    stopwatches.stop_all_at(ManualInstant::from_secs(13));
+    assert_eq!(
+        stopwatches
+            .elapsed_all_at(ManualInstant::from_secs(100))
+            .collect::<Vec<_>>(),
+        [Duration::from_secs(3), Duration::from_secs(5)],
+    );
+
+    stopwatches[0].start_at(ManualInstant::from_secs(20));
+    stopwatches.toggle_all_at(ManualInstant::from_secs(21));
+    assert!(stopwatches[0].is_stopped());
+    assert_eq!(stopwatches[1].start, Some(ManualInstant::from_secs(21)));
+}
+
+#[test]
+fn checked_reports_indices() {
+    let mut stopwatches = [
+        Stopwatch::new_started_at(ManualInstant::from_secs(0)),
+        Stopwatch::from_raw(Duration::MAX, Some(ManualInstant::from_secs(0))),
+        Stopwatch::new(),
+        Stopwatch::from_raw(Duration::MAX, Some(ManualInstant::from_secs(0))),
+    ];
+    let mut overflowed = Vec::new();
+    assert!(!stopwatches
+        .checked_stop_all_at(ManualInstant::from_secs(1), |index| overflowed.push(index)));
+    assert_eq!(overflowed, [1, 3]);
+    assert!(stopwatches[0].is_stopped());
+    assert!(stopwatches[1].is_running());
+
+    overflowed.clear();
+    assert!(!stopwatches
+        .checked_toggle_all_at(ManualInstant::from_secs(2), |index| overflowed.push(index)));
+    assert_eq!(overflowed, [1, 3]);
+    assert_eq!(stopwatches[2].start, Some(ManualInstant::from_secs(2)));
+}
+
+#[test]
+fn reset() {
+    let mut stopwatches = Vec::from([
+        Stopwatch::new_started_at(ManualInstant::from_secs(0)),
+        Stopwatch::with_elapsed(Duration::from_secs(4)),
+    ]);
+    stopwatches.reset_in_place_all_at(ManualInstant::from_secs(5));
+    assert_eq!(stopwatches[0].start, Some(ManualInstant::from_secs(5)));
+    assert_eq!(stopwatches[1].elapsed, Duration::ZERO);
+    assert!(stopwatches[1].is_stopped());
+
+    stopwatches.reset_all();
+    assert!(stopwatches.iter().all(|sw| *sw == Stopwatch::new()));
+}
+
+#[test]
+fn elapsed_all_iterator() {
+    let stopwatches = [
+        Stopwatch::new_started_at(ManualInstant::from_secs(0)),
+        Stopwatch::new_started_at(ManualInstant::from_secs(1)),
+        Stopwatch::new_started_at(ManualInstant::from_secs(2)),
+    ];
+    let mut elapsed = stopwatches.elapsed_all_at(ManualInstant::from_secs(5));
+    assert_eq!(elapsed.len(), 3);
+    assert_eq!(elapsed.next_back(), Some(Duration::from_secs(3)));
+    assert_eq!(elapsed.next(), Some(Duration::from_secs(5)));
+    assert_eq!(elapsed.len(), 1);
+}
+
+#[test]
+fn iter_one_anchor() {
+    let mut stopwatches = [Stopwatch::new(); 4];
+    stopwatches
+        .iter_mut()
+        .step_by(2)
+        .start_all_at(ManualInstant::from_secs(10));
+    assert_eq!(stopwatches.iter().filter(|sw| sw.is_running()).count(), 2);
+    assert_eq!(stopwatches[2].start, Some(ManualInstant::from_secs(10)));
+
+    stopwatches
+        .iter_mut()
+        .toggle_all_at(ManualInstant::from_secs(12));
+    assert_eq!(stopwatches[0].elapsed, Duration::from_secs(2));
+    assert_eq!(stopwatches[1].start, Some(ManualInstant::from_secs(12)));
+
+    stopwatches
+        .iter_mut()
+        .stop_all_at(ManualInstant::from_secs(13));
+    assert!(stopwatches.iter().all(Stopwatch::is_stopped));
+
+    stopwatches.iter_mut().skip(1).reset_all();
+    assert_eq!(stopwatches[0].elapsed, Duration::from_secs(2));
+    assert_eq!(stopwatches[1], Stopwatch::new());
+}
+
+#[test]
+fn iter_checked_reports_positions() {
+    let mut stopwatches = [
+        Stopwatch::from_raw(Duration::MAX, Some(ManualInstant::from_secs(0))),
+        Stopwatch::new_started_at(ManualInstant::from_secs(0)),
+        Stopwatch::from_raw(Duration::MAX, Some(ManualInstant::from_secs(0))),
+    ];
+    let mut overflowed = Vec::new();
+    assert!(!stopwatches
+        .iter_mut()
+        .skip(1)
+        .checked_stop_all_at(ManualInstant::from_secs(1), |index| overflowed.push(index)));
+    assert_eq!(overflowed, [1]);
+    assert!(stopwatches[1].is_stopped());
+    assert!(stopwatches[2].is_running());
+
+    overflowed.clear();
+    assert!(!stopwatches[..2]
+        .iter_mut()
+        .rev()
+        .checked_toggle_all_at(ManualInstant::from_secs(2), |index| overflowed.push(index)));
+    assert_eq!(overflowed, [1]);
+    assert_eq!(stopwatches[1].start, Some(ManualInstant::from_secs(2)));
+}
+
+#[test]
+fn iter_sums() {
+    let stopwatches = [
+        Stopwatch::new_started_at(ManualInstant::from_secs(1)),
+        Stopwatch::with_elapsed(Duration::from_secs(2)),
+        Stopwatch::from_raw(Duration::MAX, None),
+    ];
+    let anchor = ManualInstant::from_secs(5);
+    assert_eq!(
+        stopwatches[..2].iter().checked_sum_elapsed_at(anchor),
+        Some(Duration::from_secs(6))
+    );
+    assert_eq!(stopwatches.iter().checked_sum_elapsed_at(anchor), None);
+    assert_eq!(
+        stopwatches.iter().saturating_sum_elapsed_at(anchor),
+        Duration::MAX
+    );
+    assert_eq!(
+        stopwatches
+            .into_iter()
+            .take(1)
+            .saturating_sum_elapsed_at(anchor),
+        Duration::from_secs(4)
+    );
+}
+
+#[test]
+fn iter_starts() {
+    let stopwatches = [
+        Stopwatch::new_started_at(ManualInstant::from_secs(3)),
+        Stopwatch::with_elapsed(Duration::from_secs(2)),
+        Stopwatch::new_started_at(ManualInstant::from_secs(7)),
+        Stopwatch::new_started_at(ManualInstant::from_secs(1)),
+    ];
+    assert_eq!(
+        stopwatches.iter().latest_start(),
+        Some(ManualInstant::from_secs(7))
+    );
+    assert_eq!(
+        stopwatches.iter().earliest_start(),
+        Some(ManualInstant::from_secs(1))
+    );
+    assert_eq!(stopwatches[1..2].iter().latest_start(), None);
+    assert_eq!(stopwatches[1..2].iter().earliest_start(), None);
+}
@@ -0,0 +1,108 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+use std::vec::Vec;
+
+use super::manual::ManualInstant;
+
+type Snapshot = crate::Snapshot<ManualInstant>;
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+#[test]
+fn delta() {
+    let mut sw = Stopwatch::new_started_at(ManualInstant::from_secs(0));
+    let first = sw.snapshot_at(ManualInstant::from_secs(10));
+    sw.stop_at(ManualInstant::from_secs(15));
+    let second = sw.snapshot_at(ManualInstant::from_secs(40));
+
+    assert_eq!(
+        second,
+        Snapshot {
+            elapsed: Duration::from_secs(15),
+            running: false,
+            anchor: ManualInstant::from_secs(40),
+        },
+    );
+    assert_eq!(second.checked_sub(first), Some(Duration::from_secs(5)));
+    assert_eq!(second - first, Duration::from_secs(5));
+}
+
+#[test]
+fn delta_after_reset() {
+    let mut sw = Stopwatch::new_started_at(ManualInstant::from_secs(0));
+    let first = sw.snapshot_at(ManualInstant::from_secs(10));
+    sw.reset_in_place_at(ManualInstant::from_secs(12));
+    let second = sw.snapshot_at(ManualInstant::from_secs(14));
+
+    assert_eq!(second.checked_sub(first), None);
+    assert_eq!(second.saturating_sub(first), Duration::ZERO);
+}
+
+#[test]
+fn delta_out_of_order() {
+    let sw = Stopwatch::with_elapsed(Duration::from_secs(5));
+    let first = sw.snapshot_at(ManualInstant::from_secs(10));
+    let second = sw.snapshot_at(ManualInstant::from_secs(20));
+
+    assert_eq!(second.checked_sub(first), Some(Duration::ZERO));
+    assert_eq!(first.checked_sub(second), None);
+    assert_eq!(first.saturating_sub(second), Duration::ZERO);
+}
+
+#[test]
+#[should_panic = "attempt to subtract snapshots with underflow"]
+fn sub_underflow() {
+    let sw = Stopwatch::new_started_at(ManualInstant::from_secs(0));
+    let _ =
+        sw.snapshot_at(ManualInstant::from_secs(1)) - sw.snapshot_at(ManualInstant::from_secs(2));
+}
+
+#[test]
+fn all_at_one_anchor() {
+    let stopwatches = [
+        Stopwatch::new_started_at(ManualInstant::from_secs(0)),
+        Stopwatch::with_elapsed(Duration::from_secs(3)),
+        Stopwatch::new_started_at(ManualInstant::from_secs(5)),
+    ];
+    let snapshots = Snapshot::all_at(&stopwatches, ManualInstant::from_secs(8));
+    assert!(snapshots
+        .iter()
+        .all(|snapshot| snapshot.anchor == ManualInstant::from_secs(8)));
+    assert_eq!(
+        snapshots.map(|snapshot| snapshot.elapsed),
+        [
+            Duration::from_secs(8),
+            Duration::from_secs(3),
+            Duration::from_secs(3)
+        ]
+    );
+    assert_eq!(
+        snapshots.map(|snapshot| snapshot.running),
+        [true, false, true]
+    );
+}
+
+#[test]
+fn each_at_one_anchor() {
+    let stopwatches = Vec::from([
+        Stopwatch::new_started_at(ManualInstant::from_secs(0)),
+        Stopwatch::with_elapsed(Duration::from_secs(3)),
+    ]);
+    let snapshots = Snapshot::each_at(&stopwatches, ManualInstant::from_secs(8));
+    assert_eq!(snapshots.len(), 2);
+    assert!(snapshots
+        .clone()
+        .all(|snapshot| snapshot.anchor == ManualInstant::from_secs(8)));
+
+    let elapsed: Vec<_> = snapshots.rev().map(|snapshot| snapshot.elapsed).collect();
+    assert_eq!(elapsed, [Duration::from_secs(3), Duration::from_secs(8)]);
+
+    let mut filtered = Snapshot::each_at(
+        stopwatches.iter().filter(|sw| sw.is_stopped()),
+        ManualInstant::from_secs(8),
+    );
+    assert!(!filtered.next().unwrap().running);
+    assert_eq!(filtered.next(), None);
+}
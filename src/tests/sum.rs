@@ -0,0 +1,103 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use super::manual::ManualInstant;
+use crate::Instant;
+
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+#[test]
+fn sum_is_stopped() {
+    let stopped = [
+        Stopwatch::with_elapsed(Duration::from_secs(1)),
+        Stopwatch::with_elapsed(Duration::from_secs(2)),
+    ];
+    let sum = Stopwatch::checked_sum_at(stopped, ManualInstant::from_secs(10)).unwrap();
+    assert_eq!(sum.elapsed, Duration::from_secs(3));
+    assert!(sum.is_stopped());
+
+    let mixed = [
+        Stopwatch::new_started_at(ManualInstant::from_secs(4)),
+        Stopwatch::with_elapsed(Duration::from_secs(2)),
+    ];
+    let sum = Stopwatch::checked_sum_at(mixed, ManualInstant::from_secs(10)).unwrap();
+    assert_eq!(sum.elapsed, Duration::from_secs(8));
+    assert!(sum.is_stopped());
+
+    // two running stopwatches accumulate two seconds per second
+    let running = [
+        Stopwatch::new_started_at(ManualInstant::from_secs(4)),
+        Stopwatch::new_started_at(ManualInstant::from_secs(6)),
+    ];
+    let sum = Stopwatch::checked_sum_at(running, ManualInstant::from_secs(10)).unwrap();
+    assert_eq!(
+        sum.elapsed_at(ManualInstant::from_secs(12)),
+        Duration::from_secs(10)
+    );
+    let later = Stopwatch::checked_sum_at(running, ManualInstant::from_secs(12)).unwrap();
+    assert_eq!(later.elapsed, Duration::from_secs(14));
+
+    assert_eq!(
+        Stopwatch::checked_sum_at([], ManualInstant::from_secs(10)),
+        Some(Stopwatch::new())
+    );
+}
+
+#[test]
+fn overflow() {
+    let stopwatches = [
+        Stopwatch::with_elapsed(Duration::MAX),
+        Stopwatch::new_started_at(ManualInstant::from_secs(0)),
+    ];
+    assert_eq!(
+        Stopwatch::checked_sum_at(stopwatches, ManualInstant::from_secs(1)),
+        None
+    );
+
+    let sum = Stopwatch::saturating_sum_at(stopwatches, ManualInstant::from_secs(1));
+    assert_eq!(sum.elapsed, Duration::MAX);
+    assert!(sum.is_stopped());
+}
+
+#[test]
+fn sum_trait() {
+    let start = ManualInstant::now();
+    ManualInstant::advance(Duration::from_secs(4));
+    let stopwatches = [
+        Stopwatch::new_started_at(start),
+        Stopwatch::with_elapsed(Duration::from_secs(1)),
+    ];
+    let sum: Stopwatch = stopwatches.iter().sum();
+    assert_eq!(sum.elapsed, Duration::from_secs(5));
+    assert!(sum.is_stopped());
+
+    let total: Duration = stopwatches.into_iter().sum();
+    assert_eq!(total, Duration::from_secs(5));
+}
+
+#[test]
+#[should_panic = "attempt to sum stopwatches with overflow"]
+fn sum_trait_overflow() {
+    let stopwatches = [Stopwatch::with_elapsed(Duration::MAX); 2];
+    let _: Stopwatch = stopwatches.into_iter().sum();
+}
+
+#[test]
+fn from_iter_and_extend() {
+    let mut sw: Stopwatch = [Duration::from_secs(1), Duration::from_secs(2)]
+        .into_iter()
+        .collect();
+    assert_eq!(sw.elapsed, Duration::from_secs(3));
+    assert!(sw.is_stopped());
+
+    sw.start_at(ManualInstant::from_secs(5));
+    sw.extend([Duration::from_secs(4)]);
+    assert_eq!(sw.elapsed, Duration::from_secs(7));
+    assert_eq!(
+        sw.elapsed_at(ManualInstant::from_secs(6)),
+        Duration::from_secs(8)
+    );
+}
@@ -0,0 +1,106 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use super::manual::ManualInstant;
+
+type TaskTracker<K> = crate::TaskTracker<K, ManualInstant>;
+
+#[test]
+fn one_running() {
+    let mut tracker = TaskTracker::new_started_at(ManualInstant::from_secs(0));
+    assert!(tracker.is_idle());
+    tracker.switch_to_at('a', ManualInstant::from_secs(1));
+    tracker.switch_to_at('b', ManualInstant::from_secs(3));
+    assert_eq!(tracker.current(), Some(&'b'));
+
+    let running = tracker.iter().filter(|(_, sw)| sw.is_running()).count();
+    assert_eq!(running, 1);
+
+    tracker.idle_at(ManualInstant::from_secs(6));
+    assert!(tracker.iter().all(|(_, sw)| sw.is_stopped()));
+    assert_eq!(tracker.len(), 2);
+}
+
+#[test]
+fn totals_sum_to_wall_time() {
+    let mut tracker = TaskTracker::new_started_at(ManualInstant::from_secs(10));
+    for (i, key) in ['a', 'b', 'a', 'c', 'b'].into_iter().enumerate() {
+        tracker.switch_to_at(key, ManualInstant::from_secs(11 + 2 * i as u64));
+    }
+    tracker.idle_at(ManualInstant::from_secs(25));
+    tracker.switch_to_at('c', ManualInstant::from_secs(27));
+
+    let anchor = ManualInstant::from_secs(30);
+    assert_eq!(tracker.total_at(&'a', anchor), Duration::from_secs(4));
+    assert_eq!(tracker.total_at(&'b', anchor), Duration::from_secs(2 + 6));
+    assert_eq!(tracker.total_at(&'c', anchor), Duration::from_secs(2 + 3));
+    assert_eq!(tracker.total_at(&'d', anchor), Duration::ZERO);
+    assert_eq!(tracker.idle_total_at(anchor), Duration::from_secs(1 + 2));
+    assert_eq!(tracker.wall_total_at(anchor), Duration::from_secs(20));
+}
+
+#[test]
+fn switch_to_current() {
+    let mut tracker = TaskTracker::new_started_at(ManualInstant::from_secs(0));
+    tracker.switch_to_at("a", ManualInstant::from_secs(1));
+    tracker.switch_to_at("a", ManualInstant::from_secs(5));
+    assert_eq!(
+        tracker.get("a").unwrap().start,
+        Some(ManualInstant::from_secs(1))
+    );
+    tracker.idle_at(ManualInstant::from_secs(6));
+    tracker.idle_at(ManualInstant::from_secs(8));
+    assert_eq!(
+        tracker.idle_total_at(ManualInstant::from_secs(9)),
+        Duration::from_secs(4)
+    );
+}
+
+#[test]
+fn borrowed_keys() {
+    let mut tracker = TaskTracker::<String>::new_started_at(ManualInstant::from_secs(0));
+    tracker.switch_to_at("billing".to_string(), ManualInstant::from_secs(1));
+    tracker.idle_at(ManualInstant::from_secs(4));
+    assert_eq!(
+        tracker.total_at("billing", ManualInstant::from_secs(4)),
+        Duration::from_secs(3)
+    );
+    assert_eq!(
+        tracker
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect::<Vec<_>>(),
+        ["billing"],
+    );
+}
+
+#[test]
+fn iter() {
+    let mut tracker = TaskTracker::new_started_at(ManualInstant::from_secs(0));
+    tracker.switch_to_at('a', ManualInstant::from_secs(1));
+    tracker.switch_to_at('b', ManualInstant::from_secs(2));
+    tracker.switch_to_at('c', ManualInstant::from_secs(3));
+
+    let mut iter = tracker.iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next().map(|(key, _)| *key), Some('a'));
+    assert_eq!(iter.next_back().map(|(key, _)| *key), Some('c'));
+    assert_eq!(iter.len(), 1);
+
+    let keys =
+        |iter: crate::Tasks<'_, char, ManualInstant>| iter.map(|(key, _)| *key).collect::<Vec<_>>();
+    assert_eq!(keys(iter.clone()), ['b']);
+    assert_eq!(keys(iter), ['b']);
+    assert_eq!(
+        (&tracker)
+            .into_iter()
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>(),
+        ['a', 'b', 'c'],
+    );
+}
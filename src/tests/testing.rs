@@ -0,0 +1,112 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+use proptest::prelude::*;
+
+use super::manual::ManualInstant;
+use crate::{Instant, ReferenceStopwatch, StopwatchStrategies};
+
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+const STRATEGIES: StopwatchStrategies<ManualInstant> =
+    StopwatchStrategies::new(ManualInstant::from_secs(1_000_000));
+
+fn model(sw: Stopwatch) -> ReferenceStopwatch<ManualInstant> {
+    ReferenceStopwatch::from(sw)
+}
+
+proptest! {
+    #[test]
+    fn elapsed(sw in STRATEGIES.any(), anchor in STRATEGIES.anchor()) {
+        prop_assert_eq!(sw.checked_elapsed_at(anchor), model(sw).checked_elapsed_at(anchor));
+        prop_assert_eq!(sw.elapsed_at(anchor), model(sw).elapsed_at(anchor));
+    }
+
+    #[test]
+    fn start_stop_toggle(sw in STRATEGIES.any(), anchor in STRATEGIES.anchor()) {
+        let (mut actual, mut expected) = (sw, model(sw));
+        actual.start_at(anchor);
+        expected.start_at(anchor);
+        prop_assert!(expected.matches(&actual));
+
+        let (mut actual, mut expected) = (sw, model(sw));
+        actual.stop_at(anchor);
+        expected.stop_at(anchor);
+        prop_assert!(expected.matches(&actual));
+
+        let (mut actual, mut expected) = (sw, model(sw));
+        actual.toggle_at(anchor);
+        expected.toggle_at(anchor);
+        prop_assert!(expected.matches(&actual));
+    }
+
+    #[test]
+    fn checked_stop_toggle(sw in STRATEGIES.any(), anchor in STRATEGIES.anchor()) {
+        let (mut actual, mut expected) = (sw, model(sw));
+        prop_assert_eq!(actual.checked_stop_at(anchor), expected.checked_stop_at(anchor));
+        prop_assert!(expected.matches(&actual));
+
+        let (mut actual, mut expected) = (sw, model(sw));
+        prop_assert_eq!(actual.checked_toggle_at(anchor), expected.checked_toggle_at(anchor));
+        prop_assert!(expected.matches(&actual));
+    }
+
+    #[test]
+    fn add(sw in STRATEGIES.any(), dur in STRATEGIES.duration()) {
+        prop_assert!(model(sw).saturating_add(dur).matches(&sw.saturating_add(dur)));
+        let actual = sw.checked_add(dur);
+        let expected = model(sw).checked_add(dur);
+        prop_assert_eq!(actual.is_some(), expected.is_some());
+        if let (Some(actual), Some(expected)) = (actual, expected) {
+            prop_assert!(expected.matches(&actual));
+        }
+    }
+
+    #[test]
+    fn sub(
+        sw in STRATEGIES.any(),
+        dur in STRATEGIES.duration(),
+        anchor in STRATEGIES.anchor(),
+    ) {
+        let actual = sw.saturating_sub_at(dur, anchor);
+        prop_assert!(model(sw).saturating_sub_at(dur, anchor).matches(&actual));
+        let actual = sw.checked_sub_at(dur, anchor);
+        let expected = model(sw).checked_sub_at(dur, anchor);
+        prop_assert_eq!(actual.is_some(), expected.is_some());
+        if let (Some(actual), Some(expected)) = (actual, expected) {
+            prop_assert!(expected.matches(&actual));
+        }
+    }
+
+    #[test]
+    fn convert(
+        sw in STRATEGIES.any(),
+        anchor_i in STRATEGIES.anchor(),
+        anchor_j in STRATEGIES.anchor(),
+    ) {
+        let actual = sw.checked_convert_at(anchor_i, anchor_j);
+        let expected = model(sw).checked_convert_at(anchor_i, anchor_j);
+        prop_assert_eq!(actual.is_some(), expected.is_some());
+        if let (Some(actual), Some(expected)) = (actual, expected) {
+            prop_assert!(expected.matches(&actual));
+        }
+        let actual = sw.convert_at(anchor_i, anchor_j);
+        prop_assert!(model(sw).convert_at(anchor_i, anchor_j).matches(&actual));
+    }
+
+    #[test]
+    fn strategies(
+        stopped in STRATEGIES.stopped(),
+        running in STRATEGIES.running(),
+        near_overflow in STRATEGIES.near_overflow(),
+        unbounded in STRATEGIES.unbounded(),
+    ) {
+        prop_assert!(stopped.is_stopped());
+        prop_assert!(running.is_running());
+        let threshold = Duration::MAX.saturating_sub(Duration::from_secs(1000));
+        prop_assert!(near_overflow.elapsed >= threshold);
+        prop_assert!(unbounded.start.unwrap().checked_sub(unbounded.elapsed).is_none());
+    }
+}
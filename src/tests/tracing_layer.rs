@@ -0,0 +1,137 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::fmt::Debug;
+use core::time::Duration;
+use std::string::String;
+use std::sync::{Arc, Mutex};
+use std::vec::Vec;
+use tracing::field::{Field, Visit};
+use tracing::span::Id;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+use super::manual::ManualInstant;
+use super::DELAY;
+use crate::{SpanTimings, StopwatchLayer};
+
+/// Collects the `close` events emitted by [`StopwatchLayer`] as `(span name,
+/// busy, idle)`, and the timings of spans as they close.
+#[derive(Clone, Default)]
+struct Collector {
+    events: Arc<Mutex<Vec<(String, String, String)>>>,
+    timings: Arc<Mutex<Vec<SpanTimings<ManualInstant>>>>,
+}
+
+#[derive(Default)]
+struct Fields {
+    busy: String,
+    idle: String,
+}
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        match field.name() {
+            "busy" => self.busy = format!("{value:?}"),
+            "idle" => self.idle = format!("{value:?}"),
+            _ => {}
+        }
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Collector {
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if event.metadata().target() != "libsw_core::tracing" {
+            return;
+        }
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let parent = ctx.event_span(event).unwrap().name().into();
+        self.events
+            .lock()
+            .unwrap()
+            .push((parent, fields.busy, fields.idle));
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let span = ctx.span(&id).unwrap();
+        let timings = *span
+            .extensions()
+            .get::<SpanTimings<ManualInstant>>()
+            .unwrap();
+        self.timings.lock().unwrap().push(timings);
+    }
+}
+
+#[test]
+fn busy_and_idle() {
+    let collector = Collector::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(StopwatchLayer::<ManualInstant>::new())
+        .with(collector.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("work");
+        ManualInstant::advance(DELAY); // idle
+        span.in_scope(|| ManualInstant::advance(DELAY * 2)); // busy
+        ManualInstant::advance(DELAY * 3); // idle
+        span.in_scope(|| ManualInstant::advance(DELAY * 4)); // busy
+        drop(span);
+        ManualInstant::advance(DELAY); // closed
+    });
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(*events, [("work".into(), "600ms".into(), "400ms".into())]);
+
+    let timings = collector.timings.lock().unwrap();
+    assert_eq!(timings.len(), 1);
+    assert!(timings[0].busy.is_stopped());
+    assert!(timings[0].idle.is_stopped());
+    assert_eq!(timings[0].busy.elapsed, DELAY * 6);
+    assert_eq!(timings[0].idle.elapsed, DELAY * 4);
+}
+
+#[test]
+fn nested_spans() {
+    let collector = Collector::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(StopwatchLayer::<ManualInstant>::new())
+        .with(collector.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::debug_span!("outer").in_scope(|| {
+            ManualInstant::advance(DELAY);
+            tracing::trace_span!("inner").in_scope(|| ManualInstant::advance(DELAY));
+        });
+    });
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(
+        *events,
+        [
+            ("inner".into(), "100ms".into(), "0ns".into()),
+            ("outer".into(), "200ms".into(), "0ns".into()),
+        ]
+    );
+}
+
+#[test]
+fn span_timings() {
+    let t0 = ManualInstant::from_secs(1);
+    let t1 = ManualInstant::from_secs(2);
+    let t2 = ManualInstant::from_secs(4);
+
+    let mut timings = SpanTimings::new_at(t0);
+    assert!(timings.busy.is_stopped());
+    assert!(timings.idle.is_running());
+
+    timings.enter_at(t1);
+    assert!(timings.busy.is_running());
+    assert!(timings.idle.is_stopped());
+
+    timings.close_at(t2);
+    assert_eq!(timings.busy.elapsed, Duration::from_secs(2));
+    assert_eq!(timings.idle.elapsed, Duration::from_secs(1));
+}
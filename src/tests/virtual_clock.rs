@@ -0,0 +1,139 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+use std::boxed::Box;
+
+use super::manual::ManualInstant;
+use crate::{Instant, VirtualClock, VirtualDomain, VirtualInstant};
+
+/// Declares a domain with its own clock, so tests don't interfere. Like
+/// [`ManualInstant`], each thread has its own clock.
+macro_rules! domain {
+    ($name:ident) => {
+        struct $name;
+
+        impl VirtualDomain for $name {
+            type Parent = ManualInstant;
+
+            fn clock() -> &'static VirtualClock<ManualInstant> {
+                thread_local! {
+                    static CLOCK: &'static VirtualClock<ManualInstant> =
+                        Box::leak(Box::new(VirtualClock::new()));
+                }
+                CLOCK.with(|clock| *clock)
+            }
+        }
+    };
+}
+
+#[test]
+fn epoch_is_first_observation() {
+    let clock = VirtualClock::new();
+    assert_eq!(clock.now_at(ManualInstant::from_secs(10)), Duration::ZERO);
+    assert_eq!(
+        clock.now_at(ManualInstant::from_secs(15)),
+        Duration::from_secs(5)
+    );
+    assert_eq!(clock.now_at(ManualInstant::from_secs(5)), Duration::ZERO);
+}
+
+#[test]
+fn pause_and_resume() {
+    let clock = VirtualClock::new();
+    clock.pause_at(ManualInstant::from_secs(10));
+    assert!(clock.is_paused());
+    assert_eq!(clock.now_at(ManualInstant::from_secs(20)), Duration::ZERO);
+
+    clock.resume_at(ManualInstant::from_secs(20));
+    assert!(!clock.is_paused());
+    assert_eq!(
+        clock.now_at(ManualInstant::from_secs(23)),
+        Duration::from_secs(3)
+    );
+
+    clock.pause_at(ManualInstant::from_secs(25));
+    clock.resume_at(ManualInstant::from_secs(100));
+    assert_eq!(
+        clock.now_at(ManualInstant::from_secs(101)),
+        Duration::from_secs(6)
+    );
+}
+
+#[test]
+fn rate() {
+    let clock = VirtualClock::new();
+    let _ = clock.now_at(ManualInstant::from_secs(0));
+    clock.set_rate_at(2.0, ManualInstant::from_secs(10));
+    assert_eq!(
+        clock.now_at(ManualInstant::from_secs(15)),
+        Duration::from_secs(20)
+    );
+
+    clock.set_rate_at(0.5, ManualInstant::from_secs(20));
+    assert_eq!(
+        clock.now_at(ManualInstant::from_secs(24)),
+        Duration::from_secs(32)
+    );
+    assert!((clock.rate() - 0.5).abs() < f64::EPSILON);
+
+    clock.set_rate_at(0.0, ManualInstant::from_secs(24));
+    assert_eq!(
+        clock.now_at(ManualInstant::from_secs(1000)),
+        Duration::from_secs(32)
+    );
+}
+
+#[test]
+#[should_panic = "virtual clock rate must be finite and non-negative"]
+fn negative_rate() {
+    VirtualClock::<ManualInstant>::new().set_rate(-1.0);
+}
+
+#[test]
+fn stopwatch() {
+    domain!(Game);
+    type Stopwatch = crate::Stopwatch<VirtualInstant<Game>>;
+
+    let mut sw = Stopwatch::new_started();
+    ManualInstant::advance(Duration::from_secs(2));
+    assert_eq!(sw.elapsed(), Duration::from_secs(2));
+
+    Game::clock().pause();
+    ManualInstant::advance(Duration::from_secs(60));
+    assert_eq!(sw.elapsed(), Duration::from_secs(2));
+
+    Game::clock().set_rate(3.0);
+    Game::clock().resume();
+    ManualInstant::advance(Duration::from_secs(1));
+    assert_eq!(sw.elapsed(), Duration::from_secs(5));
+
+    sw.stop();
+    assert_eq!(
+        VirtualInstant::<Game>::now().since_epoch(),
+        Duration::from_secs(5)
+    );
+}
+
+#[test]
+fn out_of_order_anchors() {
+    let clock = VirtualClock::new();
+    let _ = clock.now_at(ManualInstant::from_secs(0));
+    clock.pause_at(ManualInstant::from_secs(20));
+    clock.resume_at(ManualInstant::from_secs(10));
+    assert_eq!(
+        clock.now_at(ManualInstant::from_secs(20)),
+        Duration::from_secs(20)
+    );
+    assert_eq!(
+        clock.now_at(ManualInstant::from_secs(25)),
+        Duration::from_secs(25)
+    );
+
+    clock.set_rate_at(2.0, ManualInstant::from_secs(15));
+    assert_eq!(
+        clock.now_at(ManualInstant::from_secs(25)),
+        Duration::from_secs(30)
+    );
+}
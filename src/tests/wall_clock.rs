@@ -0,0 +1,92 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+use std::time::SystemTime;
+
+use super::manual::ManualInstant;
+use super::DELAY;
+use crate::{WallClock, WallStopwatch};
+
+type Stopwatch = crate::Stopwatch<ManualInstant>;
+
+fn system(secs: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+fn reference() -> WallClock<ManualInstant> {
+    WallClock::new(ManualInstant::from_secs(100), system(1_000_000))
+}
+
+#[test]
+fn system_time_at() {
+    let reference = reference();
+    assert_eq!(
+        reference.system_time_at(ManualInstant::from_secs(100)),
+        Some(system(1_000_000))
+    );
+    assert_eq!(
+        reference.system_time_at(ManualInstant::from_secs(160)),
+        Some(system(1_000_060))
+    );
+    assert_eq!(
+        reference.system_time_at(ManualInstant::from_secs(40)),
+        Some(system(999_940))
+    );
+}
+
+#[test]
+fn start_and_stop() {
+    let mut sw = WallStopwatch::with_reference(Stopwatch::with_elapsed(DELAY), reference());
+    assert_eq!(sw.started_at_system(), None);
+
+    assert_eq!(
+        sw.start_at(ManualInstant::from_secs(110)),
+        Some(system(1_000_010))
+    );
+    assert_eq!(sw.started_at_system(), Some(system(1_000_010)));
+    assert_eq!(sw.start_at(ManualInstant::from_secs(120)), None);
+    assert_eq!(sw.started_at_system(), Some(system(1_000_010)));
+
+    assert_eq!(
+        sw.stop_at(ManualInstant::from_secs(130)),
+        Some(system(1_000_030))
+    );
+    assert_eq!(sw.started_at_system(), None);
+    assert_eq!(sw.stopwatch.elapsed, DELAY + Duration::from_secs(20));
+
+    assert_eq!(sw.stop_at(ManualInstant::from_secs(140)), None);
+    assert_eq!(sw.stopwatch.elapsed, DELAY + Duration::from_secs(20));
+}
+
+#[test]
+fn completes_at_system() {
+    let mut sw = WallStopwatch::with_reference(
+        Stopwatch::from_raw(Duration::from_secs(10), Some(ManualInstant::from_secs(100))),
+        reference(),
+    );
+
+    assert_eq!(
+        sw.completes_at_system(Duration::from_secs(60)),
+        Some(system(1_000_050))
+    );
+    // already reached
+    assert_eq!(
+        sw.completes_at_system(Duration::from_secs(4)),
+        Some(system(999_994))
+    );
+
+    sw.stop_at(ManualInstant::from_secs(120));
+    assert_eq!(sw.completes_at_system(Duration::from_secs(60)), None);
+}
+
+#[test]
+fn now() {
+    let before = SystemTime::now();
+    let reference = WallClock::<std::time::Instant>::now();
+    assert!(reference.system >= before);
+
+    let sw = WallStopwatch::new(crate::Sw::new_started());
+    assert!(sw.started_at_system().unwrap() >= reference.system);
+}
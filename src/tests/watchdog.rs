@@ -0,0 +1,135 @@
+// libsw: stopwatch library (tests)
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use core::time::Duration;
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant as StdInstant;
+
+use super::manual::ManualInstant;
+use crate::{Instant, WatchdogMonitor};
+
+type Watchdog = crate::Watchdog<ManualInstant>;
+
+static SHARED_NOW: AtomicU64 = AtomicU64::new(0);
+
+/// A deterministic [`Instant`] shared by every thread, unlike
+/// [`ManualInstant`]. Only one test may use it.
+#[derive(Clone, Copy, Debug)]
+struct SharedInstant(ManualInstant);
+
+impl SharedInstant {
+    fn advance(dur: Duration) {
+        let nanos = u64::try_from(dur.as_nanos()).unwrap();
+        SHARED_NOW.fetch_add(nanos, Ordering::SeqCst);
+    }
+}
+
+impl Instant for SharedInstant {
+    fn now() -> Self {
+        let nanos = SHARED_NOW.load(Ordering::SeqCst);
+        Self(ManualInstant(Duration::from_nanos(nanos)))
+    }
+
+    fn checked_add(&self, duration: Duration) -> Option<Self> {
+        self.0.checked_add(duration).map(Self)
+    }
+
+    fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        self.0.checked_sub(duration).map(Self)
+    }
+
+    fn saturating_duration_since(&self, earlier: Self) -> Duration {
+        self.0.saturating_duration_since(earlier.0)
+    }
+}
+
+/// Waits up to five seconds of real time for `condition` to hold.
+fn wait_for(mut condition: impl FnMut() -> bool) -> bool {
+    let start = StdInstant::now();
+    while start.elapsed() < Duration::from_secs(5) {
+        if condition() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    false
+}
+
+#[test]
+fn starvation() {
+    let mut watchdog =
+        Watchdog::new_started_at(Duration::from_secs(5), ManualInstant::from_secs(10));
+    assert!(!watchdog.is_starved_at(ManualInstant::from_secs(14)));
+    assert_eq!(
+        watchdog.time_until_starvation_at(ManualInstant::from_secs(14)),
+        Duration::from_secs(1)
+    );
+    assert!(watchdog.is_starved_at(ManualInstant::from_secs(15)));
+    assert_eq!(
+        watchdog.time_until_starvation_at(ManualInstant::from_secs(20)),
+        Duration::ZERO
+    );
+    assert_eq!(
+        watchdog.starvation_instant(),
+        Some(ManualInstant::from_secs(15))
+    );
+
+    watchdog.kick_at(ManualInstant::from_secs(20));
+    assert!(!watchdog.is_starved_at(ManualInstant::from_secs(24)));
+    assert_eq!(
+        watchdog.since_kick_at(ManualInstant::from_secs(24)),
+        Duration::from_secs(4)
+    );
+}
+
+#[test]
+fn stale_kicks() {
+    let mut watchdog =
+        Watchdog::new_started_at(Duration::from_secs(5), ManualInstant::from_secs(10));
+    watchdog.kick_at(ManualInstant::from_secs(8));
+    assert_eq!(watchdog.last_kick(), ManualInstant::from_secs(10));
+    assert_eq!(
+        watchdog.since_kick_at(ManualInstant::from_secs(9)),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn monitor() {
+    let fired = Arc::new(AtomicU32::new(0));
+    let watchdog = crate::Watchdog::<SharedInstant>::new_started(Duration::from_secs(5));
+    let monitor = {
+        let fired = Arc::clone(&fired);
+        WatchdogMonitor::spawn(watchdog, Duration::from_millis(1), move || {
+            fired.fetch_add(1, Ordering::SeqCst);
+        })
+    };
+    let handle = monitor.handle();
+
+    SharedInstant::advance(Duration::from_secs(4));
+    thread::sleep(Duration::from_millis(20));
+    assert!(!monitor.is_starved());
+
+    SharedInstant::advance(Duration::from_secs(1));
+    assert!(wait_for(|| monitor.is_starved()));
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(fired.load(Ordering::SeqCst), 1);
+
+    // kicking re-arms the callback
+    handle.kick();
+    assert!(!handle.is_starved());
+    SharedInstant::advance(Duration::from_secs(5));
+    assert!(wait_for(|| fired.load(Ordering::SeqCst) == 2));
+    assert!(handle.is_starved());
+
+    // a stale kick is ignored, so the watchdog stays starved
+    handle.kick_at(SharedInstant(ManualInstant::EPOCH));
+    assert!(handle.is_starved());
+    assert!(monitor.is_starved());
+
+    drop(monitor);
+    assert_eq!(handle.watchdog().timeout(), Duration::from_secs(5));
+}
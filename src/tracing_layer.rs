@@ -0,0 +1,201 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate tracing;
+extern crate tracing_subscriber;
+
+use core::marker::PhantomData;
+use core::time::Duration;
+use tracing::span::{Attributes, Id};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::{Instant, Stopwatch};
+
+/// A [`Layer`] which measures how long each span is busy and idle.
+///
+/// A span is busy while it is entered, and idle between its creation and its
+/// first entry, as well as between entries. The timings of each span are
+/// stored as [`SpanTimings`] in its extensions.
+///
+/// When a span closes, an event is emitted with the span as its parent,
+/// recording the total busy and idle time as the `busy` and `idle` fields.
+/// The event has the target `libsw_core::tracing` and the same level as the
+/// span.
+///
+/// # Notes
+///
+/// If a span is entered again before it exits (as in, on another thread),
+/// the busy stopwatch is [restarted](Stopwatch::start_at), so time is only
+/// measured from the most recent entry.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::StopwatchLayer;
+/// # use tracing_subscriber::layer::SubscriberExt;
+/// let subscriber = tracing_subscriber::registry()
+///     .with(StopwatchLayer::<std::time::Instant>::new());
+///
+/// tracing::subscriber::with_default(subscriber, || {
+///     let span = tracing::info_span!("work");
+///     span.in_scope(|| { /* busy */ });
+/// });
+/// ```
+pub struct StopwatchLayer<I: Instant> {
+    _instant: PhantomData<fn() -> I>,
+}
+
+impl<I: Instant> StopwatchLayer<I> {
+    /// Returns a new layer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            _instant: PhantomData,
+        }
+    }
+}
+
+impl<I: Instant> Default for StopwatchLayer<I> {
+    /// Returns the default layer. Same as calling [`StopwatchLayer::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Instant> Clone for StopwatchLayer<I> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Instant> core::fmt::Debug for StopwatchLayer<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StopwatchLayer").finish()
+    }
+}
+
+/// The busy and idle time of a span, measured by a [`StopwatchLayer`].
+///
+/// Exactly one of the stopwatches is running while the span is open.
+#[derive(Clone, Copy, Debug)]
+pub struct SpanTimings<I: Instant> {
+    /// Time spent while the span was entered.
+    pub busy: Stopwatch<I>,
+    /// Time spent while the span was open, but not entered.
+    pub idle: Stopwatch<I>,
+}
+
+impl<I: Instant> SpanTimings<I> {
+    /// Returns timings of a span created at `anchor`, which is idle.
+    #[must_use]
+    pub const fn new_at(anchor: I) -> Self {
+        Self {
+            busy: Stopwatch::new(),
+            idle: Stopwatch::new_started_at(anchor),
+        }
+    }
+
+    /// Marks the span as entered, as if the current time were `anchor`.
+    pub fn enter_at(&mut self, anchor: I) {
+        self.idle.stop_at(anchor);
+        self.busy.start_at(anchor);
+    }
+
+    /// Marks the span as exited, as if the current time were `anchor`.
+    pub fn exit_at(&mut self, anchor: I) {
+        self.busy.stop_at(anchor);
+        self.idle.start_at(anchor);
+    }
+
+    /// Marks the span as closed, as if the current time were `anchor`.
+    pub fn close_at(&mut self, anchor: I) {
+        self.busy.stop_at(anchor);
+        self.idle.stop_at(anchor);
+    }
+}
+
+impl<S, I> Layer<S> for StopwatchLayer<I>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    I: Instant + Send + Sync + 'static,
+{
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let timings = SpanTimings::<I>::new_at(I::now());
+            span.extensions_mut().insert(timings);
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        with_timings::<S, I>(id, &ctx, SpanTimings::enter_at);
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        with_timings::<S, I>(id, &ctx, SpanTimings::exit_at);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(&id) {
+            let timings = span
+                .extensions_mut()
+                .get_mut::<SpanTimings<I>>()
+                .map(|timings| {
+                    timings.close_at(I::now());
+                    *timings
+                });
+            if let Some(timings) = timings {
+                emit_close(
+                    &id,
+                    *span.metadata().level(),
+                    timings.busy.elapsed,
+                    timings.idle.elapsed,
+                );
+            }
+        }
+    }
+}
+
+/// Applies `f` to the timings of the span `id`, if it has any.
+fn with_timings<S, I>(id: &Id, ctx: &Context<'_, S>, f: fn(&mut SpanTimings<I>, I))
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    I: Instant + Send + Sync + 'static,
+{
+    if let Some(span) = ctx.span(id) {
+        if let Some(timings) = span.extensions_mut().get_mut::<SpanTimings<I>>() {
+            f(timings, I::now());
+        }
+    }
+}
+
+/// Emits the event recording the totals of a closed span.
+fn emit_close(id: &Id, level: Level, busy: Duration, idle: Duration) {
+    // the level of an event must be known statically
+    macro_rules! emit {
+        ($level:expr) => {
+            tracing::event!(
+                target: "libsw_core::tracing",
+                parent: id,
+                $level,
+                busy = ?busy,
+                idle = ?idle,
+                "close"
+            )
+        };
+    }
+
+    if level == Level::TRACE {
+        emit!(Level::TRACE);
+    } else if level == Level::DEBUG {
+        emit!(Level::DEBUG);
+    } else if level == Level::INFO {
+        emit!(Level::INFO);
+    } else if level == Level::WARN {
+        emit!(Level::WARN);
+    } else {
+        emit!(Level::ERROR);
+    }
+}
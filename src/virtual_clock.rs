@@ -0,0 +1,371 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate std;
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::time::Duration;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::Instant;
+
+/// A clock whose time is derived from a parent clock `I`, but can be paused
+/// and scaled.
+///
+/// Virtual time starts at zero when the clock is first observed, and then
+/// advances at [`rate`](Self::rate) times the speed of the parent clock while
+/// it is not paused.
+///
+/// To use virtual time with a [`Stopwatch`](crate::Stopwatch), the clock is
+/// given a `'static` lifetime and named by a [`VirtualDomain`]. Every
+/// stopwatch using the domain's [`VirtualInstant`]s then respects pausing and
+/// scaling of the clock.
+///
+/// # Examples
+///
+/// A leaked thread-local clock is enough if the domain is only used from a
+/// single thread. Every thread sees its own clock.
+///
+/// ```
+/// # use libsw_core::{Stopwatch, VirtualClock, VirtualDomain, VirtualInstant};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// struct Game;
+///
+/// impl VirtualDomain for Game {
+///     type Parent = Instant;
+///
+///     fn clock() -> &'static VirtualClock<Instant> {
+///         thread_local! {
+///             static CLOCK: &'static VirtualClock<Instant> =
+///                 Box::leak(Box::new(VirtualClock::new()));
+///         }
+///         CLOCK.with(|clock| *clock)
+///     }
+/// }
+///
+/// let mut cooldown = Stopwatch::<VirtualInstant<Game>>::new_started();
+///
+/// // the whole game is paused, so no stopwatch makes progress
+/// Game::clock().pause();
+/// let then = cooldown.elapsed();
+/// std::thread::sleep(Duration::from_millis(10));
+/// assert_eq!(cooldown.elapsed(), then);
+///
+/// // now it runs at double speed
+/// Game::clock().set_rate(2.0);
+/// Game::clock().resume();
+/// ```
+///
+/// To share one clock between threads, place it in a lazily initialized
+/// static, such as `std::sync::OnceLock` (which requires Rust 1.70, above this
+/// crate's minimum supported version) or `once_cell::sync::Lazy`:
+///
+/// ```ignore
+/// # use libsw_core::{VirtualClock, VirtualDomain};
+/// # use std::sync::OnceLock;
+/// # use std::time::Instant;
+/// struct Ui;
+///
+/// impl VirtualDomain for Ui {
+///     type Parent = Instant;
+///
+///     fn clock() -> &'static VirtualClock<Instant> {
+///         static CLOCK: OnceLock<VirtualClock<Instant>> = OnceLock::new();
+///         CLOCK.get_or_init(VirtualClock::new)
+///     }
+/// }
+/// ```
+pub struct VirtualClock<I: Instant> {
+    state: Mutex<State<I>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct State<I: Instant> {
+    /// The parent instant at which `base` was measured, or [`None`] if the
+    /// clock hasn't been observed yet.
+    parent: Option<I>,
+    /// The virtual time at `parent`.
+    base: Duration,
+    rate: f64,
+    paused: bool,
+}
+
+impl<I: Instant> State<I> {
+    /// Returns the virtual time at `anchor`, initializing the epoch if needed.
+    fn at(&mut self, anchor: I) -> Duration {
+        let parent = *self.parent.get_or_insert(anchor);
+        if self.paused {
+            self.base
+        } else {
+            let dt = anchor.saturating_duration_since(parent);
+            self.base.saturating_add(scale(dt, self.rate))
+        }
+    }
+
+    /// Moves the reference point of the mapping to `anchor`, without changing
+    /// virtual time. The reference point never moves backwards, so an
+    /// `anchor` before it only settles `base`.
+    fn rebase(&mut self, anchor: I) {
+        self.base = self.at(anchor);
+        let parent = self.parent.get_or_insert(anchor);
+        if anchor.saturating_duration_since(*parent) > Duration::ZERO {
+            *parent = anchor;
+        }
+    }
+}
+
+/// Scales `dur` by `rate`, saturating to [`Duration::MAX`].
+fn scale(dur: Duration, rate: f64) -> Duration {
+    #[allow(clippy::float_cmp)]
+    if rate == 1.0 {
+        return dur;
+    }
+    let secs = dur.as_secs_f64() * rate;
+    if secs < Duration::MAX.as_secs_f64() {
+        Duration::from_secs_f64(secs)
+    } else {
+        Duration::MAX
+    }
+}
+
+/// Panics if `rate` is not a valid rate for a [`VirtualClock`].
+#[track_caller]
+fn assert_rate(rate: f64) {
+    assert!(
+        rate.is_finite() && rate >= 0.0,
+        "virtual clock rate must be finite and non-negative"
+    );
+}
+
+impl<I: Instant> VirtualClock<I> {
+    /// Returns an unpaused clock running at the same rate as its parent.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                parent: None,
+                base: Duration::ZERO,
+                rate: 1.0,
+                paused: false,
+            }),
+        }
+    }
+
+    /// Returns the current virtual time.
+    #[must_use]
+    pub fn now(&self) -> Duration {
+        self.now_at(I::now())
+    }
+
+    /// Returns the virtual time, as if the current time of the parent clock
+    /// were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// `anchor` saturates to the last instant the clock was paused, resumed,
+    /// or changed rate.
+    #[must_use]
+    pub fn now_at(&self, anchor: I) -> Duration {
+        self.lock().at(anchor)
+    }
+
+    /// Returns `true` if the clock is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.lock().paused
+    }
+
+    /// Returns the rate at which virtual time advances relative to the parent
+    /// clock.
+    #[must_use]
+    pub fn rate(&self) -> f64 {
+        self.lock().rate
+    }
+
+    /// Stops virtual time from advancing.
+    pub fn pause(&self) {
+        self.rebase(None).paused = true;
+    }
+
+    /// Stops virtual time from advancing, as if the current time of the parent
+    /// clock were `anchor`.
+    pub fn pause_at(&self, anchor: I) {
+        self.rebase(Some(anchor)).paused = true;
+    }
+
+    /// Lets virtual time advance again after being paused.
+    pub fn resume(&self) {
+        self.rebase(None).paused = false;
+    }
+
+    /// Lets virtual time advance again after being paused, as if the current
+    /// time of the parent clock were `anchor`.
+    pub fn resume_at(&self, anchor: I) {
+        self.rebase(Some(anchor)).paused = false;
+    }
+
+    /// Sets the rate at which virtual time advances relative to the parent
+    /// clock. For example, `0.5` is half speed and `2.0` is double speed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is negative, infinite or NaN.
+    #[track_caller]
+    pub fn set_rate(&self, rate: f64) {
+        assert_rate(rate);
+        self.rebase(None).rate = rate;
+    }
+
+    /// Sets the rate at which virtual time advances relative to the parent
+    /// clock, as if the current time of the parent clock were `anchor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is negative, infinite or NaN.
+    #[track_caller]
+    pub fn set_rate_at(&self, rate: f64, anchor: I) {
+        assert_rate(rate);
+        self.rebase(Some(anchor)).rate = rate;
+    }
+}
+
+// private methods
+impl<I: Instant> VirtualClock<I> {
+    fn lock(&self) -> MutexGuard<'_, State<I>> {
+        // the state is always valid, even if a panic occurred while it was
+        // locked
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the state and moves its reference point to `anchor`, or to the
+    /// current time if [`None`]. The current time is read while the lock is
+    /// held, so concurrent updates are applied in chronological order.
+    fn rebase(&self, anchor: Option<I>) -> MutexGuard<'_, State<I>> {
+        let mut state = self.lock();
+        state.rebase(anchor.unwrap_or_else(I::now));
+        state
+    }
+}
+
+impl<I: Instant> Default for VirtualClock<I> {
+    /// Returns the default clock. Same as calling [`VirtualClock::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Instant> fmt::Debug for VirtualClock<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = *self.lock();
+        f.debug_struct("VirtualClock")
+            .field("parent", &state.parent)
+            .field("base", &state.base)
+            .field("rate", &state.rate)
+            .field("paused", &state.paused)
+            .finish()
+    }
+}
+
+/// A type naming the static [`VirtualClock`] used by [`VirtualInstant`]s.
+///
+/// See [`VirtualClock`] for an example.
+pub trait VirtualDomain: 'static {
+    /// The timekeeping type of the parent clock.
+    type Parent: Instant;
+
+    /// Returns the clock of the domain.
+    fn clock() -> &'static VirtualClock<Self::Parent>;
+}
+
+/// An instant in the virtual time of the domain `D`.
+///
+/// [`Instant::now`] reads the clock of `D`, so a
+/// [`Stopwatch<VirtualInstant<D>>`](crate::Stopwatch) measures virtual time.
+pub struct VirtualInstant<D: VirtualDomain> {
+    since_epoch: Duration,
+    _domain: PhantomData<fn() -> D>,
+}
+
+impl<D: VirtualDomain> VirtualInstant<D> {
+    /// Returns the instant `since_epoch` after the domain's clock was first
+    /// observed.
+    #[must_use]
+    pub const fn from_epoch(since_epoch: Duration) -> Self {
+        Self {
+            since_epoch,
+            _domain: PhantomData,
+        }
+    }
+
+    /// Returns the virtual time between when the domain's clock was first
+    /// observed and this instant.
+    #[must_use]
+    pub const fn since_epoch(&self) -> Duration {
+        self.since_epoch
+    }
+}
+
+impl<D: VirtualDomain> Instant for VirtualInstant<D> {
+    fn now() -> Self {
+        Self::from_epoch(D::clock().now())
+    }
+
+    fn checked_add(&self, duration: Duration) -> Option<Self> {
+        self.since_epoch.checked_add(duration).map(Self::from_epoch)
+    }
+
+    fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        self.since_epoch.checked_sub(duration).map(Self::from_epoch)
+    }
+
+    fn saturating_duration_since(&self, earlier: Self) -> Duration {
+        self.since_epoch.saturating_sub(earlier.since_epoch)
+    }
+}
+
+impl<D: VirtualDomain> Clone for VirtualInstant<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D: VirtualDomain> Copy for VirtualInstant<D> {}
+
+impl<D: VirtualDomain> fmt::Debug for VirtualInstant<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VirtualInstant")
+            .field(&self.since_epoch)
+            .finish()
+    }
+}
+
+impl<D: VirtualDomain> PartialEq for VirtualInstant<D> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.since_epoch == rhs.since_epoch
+    }
+}
+
+impl<D: VirtualDomain> Eq for VirtualInstant<D> {}
+
+impl<D: VirtualDomain> PartialOrd for VirtualInstant<D> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<D: VirtualDomain> Ord for VirtualInstant<D> {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.since_epoch.cmp(&rhs.since_epoch)
+    }
+}
+
+impl<D: VirtualDomain> Hash for VirtualInstant<D> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.since_epoch.hash(state);
+    }
+}
@@ -0,0 +1,189 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate std;
+
+use core::time::Duration;
+use std::time::SystemTime;
+
+use crate::{Instant, Stopwatch};
+
+/// A reference sample pairing an [`Instant`] with the [`SystemTime`] at the
+/// same moment.
+///
+/// This maps instants of a monotonic clock to wall-clock time, without relying
+/// on the system clock for measurement. If the system clock is adjusted after
+/// the sample is taken, mapped times will not reflect the adjustment.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::WallClock;
+/// # use core::time::Duration;
+/// # use std::time::{Instant, SystemTime};
+/// let reference = WallClock::<Instant>::now();
+/// let later = reference.instant + Duration::from_secs(60);
+/// assert_eq!(
+///     reference.system_time_at(later),
+///     Some(reference.system + Duration::from_secs(60)),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WallClock<I: Instant> {
+    /// The instant at which the sample was taken.
+    pub instant: I,
+    /// The system time at which the sample was taken.
+    pub system: SystemTime,
+}
+
+impl<I: Instant> WallClock<I> {
+    /// Returns a reference sample of the current instant and system time.
+    #[must_use]
+    pub fn now() -> Self {
+        Self::new(I::now(), SystemTime::now())
+    }
+
+    /// Returns a reference sample pairing `instant` with `system`.
+    #[must_use]
+    pub const fn new(instant: I, system: SystemTime) -> Self {
+        Self { instant, system }
+    }
+
+    /// Returns the system time corresponding to `anchor`. If it is not
+    /// representable, returns [`None`].
+    #[must_use]
+    pub fn system_time_at(&self, anchor: I) -> Option<SystemTime> {
+        // see `Stopwatch::saturate_anchor_to_start` for why this orders them
+        let future = anchor.saturating_duration_since(self.instant);
+        let past = self.instant.saturating_duration_since(anchor);
+        if future < past {
+            self.system.checked_sub(past)
+        } else {
+            self.system.checked_add(future)
+        }
+    }
+}
+
+/// A [`Stopwatch`] paired with a [`WallClock`] reference sample, which reports
+/// its events in wall-clock time.
+///
+/// Unlike [`SystemSw`](crate::SystemSw), time is still measured with the
+/// monotonic timekeeping type `I`, so changes to the system clock do not
+/// affect the elapsed time.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{Sw, WallStopwatch};
+/// # use core::time::Duration;
+/// let mut sw = WallStopwatch::new(Sw::new_started());
+/// println!("started at {:?}", sw.started_at_system());
+///
+/// let eta = sw.completes_at_system(Duration::from_secs(60));
+/// println!("will reach one minute at {eta:?}");
+///
+/// let stopped = sw.stop();
+/// println!("stopped at {stopped:?}");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct WallStopwatch<I: Instant> {
+    /// The stopwatch measuring elapsed time.
+    pub stopwatch: Stopwatch<I>,
+    /// The reference sample used to map instants to wall-clock time.
+    pub reference: WallClock<I>,
+}
+
+impl<I: Instant> WallStopwatch<I> {
+    /// Pairs `stopwatch` with a reference sample of the current instant and
+    /// system time.
+    #[must_use]
+    pub fn new(stopwatch: Stopwatch<I>) -> Self {
+        Self::with_reference(stopwatch, WallClock::now())
+    }
+
+    /// Pairs `stopwatch` with the reference sample `reference`.
+    #[must_use]
+    pub const fn with_reference(stopwatch: Stopwatch<I>, reference: WallClock<I>) -> Self {
+        Self {
+            stopwatch,
+            reference,
+        }
+    }
+
+    /// Returns the system time at which the stopwatch was last started. If it
+    /// is stopped, or the system time is not representable, returns [`None`].
+    #[must_use]
+    pub fn started_at_system(&self) -> Option<SystemTime> {
+        self.reference.system_time_at(self.stopwatch.start?)
+    }
+
+    /// Starts measuring the time elapsed, returning the system time of the
+    /// start. If the stopwatch was already running, or the system time is not
+    /// representable, returns [`None`].
+    pub fn start(&mut self) -> Option<SystemTime> {
+        self.start_at(I::now())
+    }
+
+    /// Starts measuring the time elapsed as if the current time were
+    /// `anchor`, returning the system time of the start. If the stopwatch was
+    /// already running, or the system time is not representable, returns
+    /// [`None`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::start_at`] for notes about the chronology of
+    /// `anchor`.
+    pub fn start_at(&mut self, anchor: I) -> Option<SystemTime> {
+        if self.stopwatch.is_running() {
+            return None;
+        }
+        self.stopwatch.start_at(anchor);
+        self.reference.system_time_at(anchor)
+    }
+
+    /// Stops measuring the time elapsed since the last start, returning the
+    /// system time of the stop. If the stopwatch was already stopped, or the
+    /// system time is not representable, returns [`None`].
+    pub fn stop(&mut self) -> Option<SystemTime> {
+        self.stop_at(I::now())
+    }
+
+    /// Stops measuring the time elapsed since the last start as if the current
+    /// time were `anchor`, returning the system time of the stop. If the
+    /// stopwatch was already stopped, or the system time is not representable,
+    /// returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// See [`Stopwatch::stop_at`] for notes about the chronology of `anchor`,
+    /// as well as what happens if overflow occurs.
+    pub fn stop_at(&mut self, anchor: I) -> Option<SystemTime> {
+        if self.stopwatch.is_stopped() {
+            return None;
+        }
+        self.stopwatch.stop_at(anchor);
+        self.reference.system_time_at(anchor)
+    }
+
+    /// Returns the projected system time at which the total time elapsed
+    /// reaches `target`, if the stopwatch keeps running. If it is stopped, or
+    /// the system time is not representable, returns [`None`].
+    ///
+    /// # Notes
+    ///
+    /// If the total time elapsed already exceeds `target`, this is the system
+    /// time at which it would have been reached if the stopwatch had been
+    /// running continuously.
+    #[must_use]
+    pub fn completes_at_system(&self, target: Duration) -> Option<SystemTime> {
+        let start = self.stopwatch.start?;
+        let elapsed = self.stopwatch.elapsed;
+        let completion = if elapsed <= target {
+            start.checked_add(target.saturating_sub(elapsed))?
+        } else {
+            start.checked_sub(elapsed.saturating_sub(target))?
+        };
+        self.reference.system_time_at(completion)
+    }
+}
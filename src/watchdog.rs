@@ -0,0 +1,127 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+use core::time::Duration;
+
+use crate::Instant;
+
+/// A timer which starves if it is not kicked within a timeout.
+///
+/// To watch it from a background thread, see `WatchdogMonitor`, which
+/// requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::Watchdog;
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let start = Instant::now();
+/// let at = |secs: u64| start + Duration::from_secs(secs);
+/// let mut watchdog = Watchdog::new_started_at(Duration::from_secs(5), start);
+///
+/// watchdog.kick_at(at(3));
+/// assert!(!watchdog.is_starved_at(at(7)));
+/// assert_eq!(watchdog.time_until_starvation_at(at(7)), Duration::from_secs(1));
+/// assert!(watchdog.is_starved_at(at(8)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Watchdog<I: Instant> {
+    timeout: Duration,
+    last_kick: I,
+}
+
+impl<I: Instant> Watchdog<I> {
+    /// Returns a watchdog with the given timeout, kicked now.
+    #[must_use]
+    pub fn new_started(timeout: Duration) -> Self {
+        Self::new_started_at(timeout, I::now())
+    }
+
+    /// Returns a watchdog with the given timeout, kicked at `start`.
+    #[must_use]
+    pub const fn new_started_at(timeout: Duration, start: I) -> Self {
+        Self {
+            timeout,
+            last_kick: start,
+        }
+    }
+
+    /// Returns the time allowed between kicks.
+    #[must_use]
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns the instant of the last kick.
+    #[must_use]
+    pub const fn last_kick(&self) -> I {
+        self.last_kick
+    }
+
+    /// Kicks the watchdog, restarting the timeout.
+    pub fn kick(&mut self) {
+        self.kick_at(I::now());
+    }
+
+    /// Kicks the watchdog as if the current time were `anchor`, restarting
+    /// the timeout. If `anchor` is before the last kick, nothing happens.
+    pub fn kick_at(&mut self, anchor: I) {
+        if self.last_kick.saturating_duration_since(anchor).is_zero() {
+            self.last_kick = anchor;
+        }
+    }
+
+    /// Returns the time elapsed since the last kick.
+    #[must_use]
+    pub fn since_kick(&self) -> Duration {
+        self.since_kick_at(I::now())
+    }
+
+    /// Returns the time elapsed since the last kick, measured as if the
+    /// current time were `anchor`.
+    ///
+    /// # Notes
+    ///
+    /// If `anchor` is before the last kick, returns [`Duration::ZERO`].
+    #[must_use]
+    pub fn since_kick_at(&self, anchor: I) -> Duration {
+        anchor.saturating_duration_since(self.last_kick)
+    }
+
+    /// Returns `true` if the watchdog has not been kicked within its timeout.
+    #[must_use]
+    pub fn is_starved(&self) -> bool {
+        self.is_starved_at(I::now())
+    }
+
+    /// Returns `true` if the watchdog had not been kicked within its timeout,
+    /// as if the current time were `anchor`.
+    #[must_use]
+    pub fn is_starved_at(&self, anchor: I) -> bool {
+        self.since_kick_at(anchor) >= self.timeout
+    }
+
+    /// Returns the time left until the watchdog starves, or
+    /// [`Duration::ZERO`] if it already has.
+    #[must_use]
+    pub fn time_until_starvation(&self) -> Duration {
+        self.time_until_starvation_at(I::now())
+    }
+
+    /// Returns the time left until the watchdog starves, or
+    /// [`Duration::ZERO`] if it already has, measured as if the current time
+    /// were `anchor`.
+    #[must_use]
+    pub fn time_until_starvation_at(&self, anchor: I) -> Duration {
+        self.timeout.saturating_sub(self.since_kick_at(anchor))
+    }
+
+    /// Returns the instant the watchdog starves unless it is kicked, or
+    /// [`None`] if it is not representable by `I`.
+    #[must_use]
+    pub fn starvation_instant(&self) -> Option<I> {
+        self.last_kick.checked_add(self.timeout)
+    }
+}
@@ -0,0 +1,187 @@
+// libsw: stopwatch library
+// copyright (C) 2022-2023 Ula Shipman <ula.hello@mailbox.org>
+// licensed under MIT OR Apache-2.0
+
+extern crate std;
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+
+use crate::canonical::instant_eq;
+use crate::{Instant, Watchdog};
+
+/// A background thread which watches a [`Watchdog`], setting a flag and
+/// calling a callback when it starves.
+///
+/// Workers prove their liveness by kicking the watchdog through a
+/// [`WatchdogHandle`]. The monitor checks the watchdog every poll interval,
+/// and calls the callback once each time it starves. Kicking a starved
+/// watchdog clears the flag, re-arming the callback.
+///
+/// The thread is stopped and joined when the monitor is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use libsw_core::{Watchdog, WatchdogMonitor};
+/// # use core::time::Duration;
+/// # use std::time::Instant;
+/// let watchdog = Watchdog::<Instant>::new_started(Duration::from_secs(1));
+/// let monitor = WatchdogMonitor::spawn(watchdog, Duration::from_millis(5), || {
+///     eprintln!("worker is stuck");
+/// });
+///
+/// let handle = monitor.handle();
+/// std::thread::spawn(move || {
+///     for _ in 0..3 {
+///         handle.kick();
+///         std::thread::sleep(Duration::from_millis(10));
+///     }
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert!(!monitor.is_starved());
+/// ```
+#[derive(Debug)]
+pub struct WatchdogMonitor<I: Instant> {
+    shared: Arc<Shared<I>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// A handle for kicking the [`Watchdog`] of a [`WatchdogMonitor`] from any
+/// thread.
+///
+/// This is returned by [`WatchdogMonitor::handle`].
+#[derive(Clone, Debug)]
+pub struct WatchdogHandle<I: Instant> {
+    shared: Arc<Shared<I>>,
+}
+
+#[derive(Debug)]
+struct Shared<I: Instant> {
+    watchdog: Mutex<Watchdog<I>>,
+    starved: AtomicBool,
+    stopped: AtomicBool,
+}
+
+impl<I: Instant + Send + 'static> WatchdogMonitor<I> {
+    /// Spawns a thread which checks `watchdog` every `poll`, and calls
+    /// `on_starved` each time it starves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread cannot be spawned.
+    #[must_use]
+    #[track_caller]
+    pub fn spawn<F: FnMut() + Send + 'static>(
+        watchdog: Watchdog<I>,
+        poll: Duration,
+        mut on_starved: F,
+    ) -> Self {
+        let shared = Arc::new(Shared {
+            watchdog: Mutex::new(watchdog),
+            starved: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
+        });
+        let thread = {
+            let shared = Arc::clone(&shared);
+            thread::Builder::new()
+                .name("watchdog".into())
+                .spawn(move || {
+                    while !shared.stopped.load(Ordering::Acquire) {
+                        // the flag is set while locked, so a concurrent kick
+                        // either happens before the check or clears it after
+                        let watchdog = shared.lock();
+                        let newly_starved =
+                            watchdog.is_starved() && !shared.starved.swap(true, Ordering::AcqRel);
+                        drop(watchdog);
+                        if newly_starved {
+                            on_starved();
+                        }
+                        thread::park_timeout(poll);
+                    }
+                })
+                .expect("failed to spawn watchdog thread")
+        };
+        Self {
+            shared,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl<I: Instant> WatchdogMonitor<I> {
+    /// Returns a handle for kicking the watchdog.
+    #[must_use]
+    pub fn handle(&self) -> WatchdogHandle<I> {
+        WatchdogHandle {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+
+    /// Returns `true` if the monitor has observed the watchdog starve since
+    /// its last kick.
+    #[must_use]
+    pub fn is_starved(&self) -> bool {
+        self.shared.starved.load(Ordering::Acquire)
+    }
+}
+
+impl<I: Instant> Drop for WatchdogMonitor<I> {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            // a panic in the callback has already been reported by the thread
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<I: Instant> WatchdogHandle<I> {
+    /// Kicks the watchdog, restarting the timeout.
+    pub fn kick(&self) {
+        self.kick_at(I::now());
+    }
+
+    /// Kicks the watchdog as if the current time were `anchor`, restarting
+    /// the timeout, and clears the starved flag.
+    ///
+    /// # Notes
+    ///
+    /// See [`Watchdog::kick_at`] for notes about the chronology of `anchor`.
+    /// If the kick is ignored because `anchor` is not after the last kick,
+    /// the starved flag is left unchanged.
+    pub fn kick_at(&self, anchor: I) {
+        let mut watchdog = self.shared.lock();
+        let last_kick = watchdog.last_kick();
+        watchdog.kick_at(anchor);
+        if !instant_eq(last_kick, watchdog.last_kick()) {
+            self.shared.starved.store(false, Ordering::Release);
+        }
+    }
+
+    /// Returns `true` if the monitor has observed the watchdog starve since
+    /// its last kick.
+    #[must_use]
+    pub fn is_starved(&self) -> bool {
+        self.shared.starved.load(Ordering::Acquire)
+    }
+
+    /// Returns a copy of the watchdog.
+    #[must_use]
+    pub fn watchdog(&self) -> Watchdog<I> {
+        *self.shared.lock()
+    }
+}
+
+impl<I: Instant> Shared<I> {
+    fn lock(&self) -> MutexGuard<'_, Watchdog<I>> {
+        // the watchdog is always valid, even if a panic occurred while it was
+        // locked
+        self.watchdog.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}